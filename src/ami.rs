@@ -0,0 +1,249 @@
+//! Automutual information of a series with its lagged copy, as a function of the lag.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::autocorrelation::first_minimum_of;
+use crate::histogram::{check_edges, joint_counts, mutual_information_of};
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::statistics::{autocorr_lag, linreg, max_, min_};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Estimator {
+    /// Assumes jointly Gaussian values: `-ln(1 - ρ²) / 2` for the lagged correlation ρ.
+    Gaussian,
+    /// Joint histogram of equal-width bins, as in `CO_HistogramAMI_even`.
+    Histogram { bins: usize },
+    /// k-nearest-neighbour estimator of Kraskov, Stögbauer & Grassberger (2004), algorithm 1.
+    Kraskov { k: usize },
+}
+
+/// Automutual information in nats at lags `1..=max_lag`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmiCurve {
+    pub lags: Vec<usize>,
+    pub ami: Vec<f64>,
+}
+
+impl AmiCurve {
+    /// Lag of the first local minimum.
+    pub fn first_minimum(&self) -> Option<usize> {
+        first_minimum_of(&self.ami).map(|i| self.lags[i])
+    }
+
+    /// Lag, linearly interpolated, at which the curve first falls below `1/e` of its value at
+    /// the first lag.
+    pub fn first_1e_crossing(&self) -> Option<f64> {
+        let thresh = self.ami.first()? / 1.0f64.exp();
+
+        for i in 1..self.ami.len() {
+            if self.ami[i] < thresh {
+                let m = self.ami[i] - self.ami[i - 1];
+                let dx = (thresh - self.ami[i - 1]) / m;
+                return Some(self.lags[i - 1] as f64 + dx);
+            }
+        }
+        None
+    }
+
+    /// Sum of the automutual information over all lags.
+    pub fn area(&self) -> f64 {
+        self.ami.iter().sum()
+    }
+
+    /// Rate `λ` of an exponential decay `A exp(-λ τ)` fitted by least squares to the logarithm
+    /// of the positive values of the curve.
+    pub fn decay_rate(&self) -> f64 {
+        let (lags, log_ami): (Vec<f64>, Vec<f64>) = self
+            .lags
+            .iter()
+            .zip(&self.ami)
+            .filter(|(_, ami)| **ami > 0.0)
+            .map(|(lag, ami)| (*lag as f64, ami.ln()))
+            .unzip();
+
+        if lags.len() < 2 {
+            return f64::NAN;
+        }
+        let (m, _) = linreg(lags.len(), &lags, &log_ami);
+        return -m;
+    }
+}
+
+/// Automutual information at lags `1..=max_lag`, clipped to two less than the series length.
+pub fn ami_curve(a: &[f64], max_lag: usize, estimator: Estimator) -> AmiCurve {
+    let max_lag = max_lag.min(a.len().saturating_sub(2));
+    let lags = (1..=max_lag).collect::<Vec<usize>>();
+
+    let ami = lags
+        .iter()
+        .map(|&lag| match estimator {
+            Estimator::Gaussian => gaussian_ami(a, lag),
+            Estimator::Histogram { bins } => histogram_ami(a, lag, bins),
+            Estimator::Kraskov { k } => kraskov_ami(a, lag, k),
+        })
+        .collect();
+
+    AmiCurve { lags, ami }
+}
+
+pub fn gaussian_ami(a: &[f64], tau: usize) -> f64 {
+    let ac = autocorr_lag(a, tau);
+    return -0.5 * (1.0 - ac * ac).ln();
+}
+
+/// Digamma function: recurrence up to x ≥ 6 followed by the asymptotic expansion.
+fn digamma(x: f64) -> f64 {
+    let mut x = x;
+    let mut result = 0.0;
+    while x < 6.0 {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    let f = 1.0 / (x * x);
+    result + x.ln()
+        - 0.5 / x
+        - f * (1.0 / 12.0 - f * (1.0 / 120.0 - f * (1.0 / 252.0 - f * (1.0 / 240.0 - f / 132.0))))
+}
+
+/// Inserts `d` into the ascending list of smallest distances if it belongs there.
+fn insert_sorted(nearest: &mut [f64], d: f64) {
+    let k = nearest.len();
+    if d < nearest[k - 1] {
+        let pos = nearest.partition_point(|&v| v <= d);
+        nearest.copy_within(pos..k - 1, pos + 1);
+        nearest[pos] = d;
+    }
+}
+
+/// Number of values in the sorted slice strictly within `eps` of `x`, or equal to `x` when `eps`
+/// is 0 so that `x` itself is always counted.
+fn count_within(sorted: &[f64], x: f64, eps: f64) -> usize {
+    let (lo, hi) = if eps > 0.0 {
+        (
+            sorted.partition_point(|&v| v <= x - eps),
+            sorted.partition_point(|&v| v < x + eps),
+        )
+    } else {
+        (
+            sorted.partition_point(|&v| v < x),
+            sorted.partition_point(|&v| v <= x),
+        )
+    };
+    hi - lo
+}
+
+/// KSG estimate of the mutual information between `a[t]` and `a[t + tau]` with `k` neighbours
+/// in the max-norm. Neighbours are found by scanning outwards from each point in order of its
+/// first coordinate, stopping once that distance alone exceeds the current k-th distance.
+/// Series with `NaN` values give `NaN`.
+pub fn kraskov_ami(a: &[f64], tau: usize, k: usize) -> f64 {
+    let n = a.len().saturating_sub(tau);
+    if k == 0 || n <= k || a.iter().any(|v| v.is_nan()) {
+        return f64::NAN;
+    }
+    let x = &a[..n];
+    let y = &a[tau..];
+
+    let mut order = (0..n).collect::<Vec<usize>>();
+    order.sort_unstable_by(|&i, &j| x[i].total_cmp(&x[j]));
+
+    let mut x_sorted = x.to_vec();
+    x_sorted.sort_unstable_by(f64::total_cmp);
+    let mut y_sorted = y.to_vec();
+    y_sorted.sort_unstable_by(f64::total_cmp);
+
+    // k smallest distances seen so far, ascending
+    let mut nearest = vec![f64::INFINITY; k];
+    let mut sum_psi = 0.0;
+
+    for p in 0..n {
+        let i = order[p];
+        nearest.fill(f64::INFINITY);
+
+        let (mut lo, mut hi) = (p, p + 1);
+        loop {
+            let dx_lo = if lo > 0 {
+                x[i] - x[order[lo - 1]]
+            } else {
+                f64::INFINITY
+            };
+            let dx_hi = if hi < n {
+                x[order[hi]] - x[i]
+            } else {
+                f64::INFINITY
+            };
+            let (dx, j) = if dx_lo <= dx_hi {
+                if lo == 0 {
+                    break;
+                }
+                lo -= 1;
+                (dx_lo, order[lo])
+            } else {
+                hi += 1;
+                (dx_hi, order[hi - 1])
+            };
+            if dx > nearest[k - 1] {
+                break;
+            }
+            insert_sorted(&mut nearest, dx.max((y[i] - y[j]).abs()));
+        }
+
+        let eps = nearest[k - 1];
+        // neighbours strictly closer than eps, or tied with it when eps is 0, excluding the point
+        // itself
+        let n_x = count_within(&x_sorted, x[i], eps) - 1;
+        let n_y = count_within(&y_sorted, y[i], eps) - 1;
+
+        sum_psi += digamma((n_x + 1) as f64) + digamma((n_y + 1) as f64);
+    }
+
+    return digamma(k as f64) + digamma(n as f64) - sum_psi / n as f64;
+}
+
+/// Automutual information at lag `tau` from a joint histogram of `n_bins` equal-width bins
+/// spanning the range of the whole series, widened by 0.1 on each side.
+pub fn histogram_ami(a: &[f64], tau: usize, n_bins: usize) -> f64 {
+    histogram_ami_with(
+        a,
+        tau,
+        n_bins,
+        &mut Vec::new(),
+        &mut Vec::new(),
+        &mut Vec::new(),
+    )
+}
+
+/// [`histogram_ami`] with the edges, joint counts and marginals in reusable buffers.
+pub(crate) fn histogram_ami_with(
+    a: &[f64],
+    tau: usize,
+    n_bins: usize,
+    edges: &mut Vec<f64>,
+    counts: &mut Vec<usize>,
+    marginals: &mut Vec<f64>,
+) -> f64 {
+    let y1 = &a[..a.len() - tau];
+    let y2 = &a[tau..];
+
+    let max_val = max_(a);
+    let min_val = min_(a);
+
+    let bin_step = (max_val - min_val + 0.2) / n_bins as f64;
+
+    edges.clear();
+    edges.extend((0..n_bins + 1).map(|i| min_val + (i as f64 * bin_step) - 0.1));
+
+    if check_edges(edges).is_err() {
+        return f64::NAN;
+    }
+
+    counts.clear();
+    counts.resize(n_bins * n_bins, 0);
+    joint_counts(y1, y2, edges, edges, counts);
+
+    marginals.clear();
+    marginals.resize(2 * n_bins, 0.0);
+    mutual_information_of(counts, n_bins, n_bins, marginals)
+}
@@ -0,0 +1,154 @@
+//! Sample autocorrelation and partial autocorrelation functions with their confidence bounds.
+//!
+//! The autocorrelation is the usual biased estimator, computed through the FFT: the
+//! autocovariance at lag k is normalised by the series length, not by `n - k`.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::fft::FftBuffer;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::statistics::autocorr_into;
+use crate::symbolic::normal_quantile;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Acf {
+    /// Autocorrelation at lags `0..=max_lag`; `values[0]` is 1.
+    pub values: Vec<f64>,
+    /// Length of the series.
+    pub n: usize,
+}
+
+fn z_score(level: f64) -> f64 {
+    normal_quantile(0.5 + level / 2.0)
+}
+
+pub(crate) fn first_zero_of(r: &[f64]) -> Option<usize> {
+    r.iter().position(|&r| r.is_nan() || r <= 0.0)
+}
+
+pub(crate) fn first_1e_crossing_of(r: &[f64]) -> Option<f64> {
+    let thresh = 1.0 / 1.0f64.exp();
+
+    for i in 0..r.len().saturating_sub(1) {
+        if r[i + 1] < thresh {
+            let m = r[i + 1] - r[i];
+            let dy = thresh - r[i];
+            let dx = dy / m;
+            return Some(i as f64 + dx);
+        }
+    }
+    None
+}
+
+pub(crate) fn first_minimum_of(r: &[f64]) -> Option<usize> {
+    (1..r.len().saturating_sub(1)).find(|&i| r[i] < r[i - 1] && r[i] < r[i + 1])
+}
+
+impl Acf {
+    pub fn max_lag(&self) -> usize {
+        self.values.len().saturating_sub(1)
+    }
+
+    /// Half-width of the two-sided confidence band for the autocorrelation of white noise,
+    /// `z / √n`, at the given confidence level (e.g. 0.95).
+    pub fn white_noise_bound(&self, level: f64) -> f64 {
+        z_score(level) / (self.n as f64).sqrt()
+    }
+
+    /// Half-widths of the confidence band at each lag under Bartlett's formula, which assumes a
+    /// moving-average process of order `k - 1` at lag `k`: `z √((1 + 2 Σ_{j<k} r_j²) / n)`.
+    /// The entry for lag 0 is 0.
+    pub fn bartlett_bounds(&self, level: f64) -> Vec<f64> {
+        let z = z_score(level);
+        let mut bounds = vec![0.0; self.values.len()];
+        let mut sum_sq = 0.0;
+        for k in 1..self.values.len() {
+            bounds[k] = z * ((1.0 + 2.0 * sum_sq) / self.n as f64).sqrt();
+            sum_sq += self.values[k] * self.values[k];
+        }
+        bounds
+    }
+
+    /// First lag at which the autocorrelation is not positive or is NaN.
+    pub fn first_zero(&self) -> Option<usize> {
+        first_zero_of(&self.values)
+    }
+
+    /// Lag, linearly interpolated, at which the autocorrelation first falls below `1/e`.
+    pub fn first_1e_crossing(&self) -> Option<f64> {
+        first_1e_crossing_of(&self.values)
+    }
+
+    /// Lag of the first local minimum.
+    pub fn first_minimum(&self) -> Option<usize> {
+        first_minimum_of(&self.values)
+    }
+
+    /// Ljung–Box portmanteau statistic over lags `1..=h`,
+    /// `Q = n (n + 2) Σ r_k² / (n - k)`, approximately χ² with `h` degrees of freedom for white
+    /// noise. `h` is clipped to the computed lags.
+    pub fn ljung_box(&self, h: usize) -> f64 {
+        let n = self.n as f64;
+        let h = h.min(self.max_lag());
+        let mut q = 0.0;
+        for k in 1..=h {
+            q += self.values[k] * self.values[k] / (n - k as f64);
+        }
+        return n * (n + 2.0) * q;
+    }
+}
+
+/// Autocorrelation at lags `0..=max_lag`, with `max_lag` clipped to `x.len() - 1`.
+pub fn acf(a: &[f64], max_lag: usize) -> Acf {
+    let mut values = Vec::new();
+    acf_into(a, max_lag, &mut FftBuffer::default(), &mut values);
+
+    Acf { values, n: a.len() }
+}
+
+/// The values of [`acf`] in reusable buffers.
+pub(crate) fn acf_into(a: &[f64], max_lag: usize, fft: &mut FftBuffer, values: &mut Vec<f64>) {
+    values.clear();
+    if a.is_empty() {
+        return;
+    }
+    values.resize(max_lag.min(a.len() - 1) + 1, 0.0);
+    autocorr_into(a, fft, values);
+}
+
+/// Partial autocorrelation at lags `0..=max_lag` from the Levinson–Durbin recursion on the
+/// sample autocorrelation; `pacf[0]` is 1.
+pub fn pacf(a: &[f64], max_lag: usize) -> Vec<f64> {
+    let r = acf(a, max_lag).values;
+    if r.is_empty() {
+        return r;
+    }
+    let max_lag = r.len() - 1;
+
+    let mut out = vec![0.0; max_lag + 1];
+    out[0] = 1.0;
+
+    // coefficients of the AR(k) fit, phi[j - 1] for lag j
+    let mut phi = Vec::with_capacity(max_lag);
+    let mut prev = Vec::with_capacity(max_lag);
+    for k in 1..=max_lag {
+        let mut num = r[k];
+        let mut den = 1.0;
+        for j in 1..k {
+            num -= phi[j - 1] * r[k - j];
+            den -= phi[j - 1] * r[j];
+        }
+        let phi_kk = num / den;
+
+        prev.clone_from(&phi);
+        for j in 1..k {
+            phi[j - 1] = prev[j - 1] - phi_kk * prev[k - j - 1];
+        }
+        phi.push(phi_kk);
+        out[k] = phi_kk;
+    }
+
+    out
+}
@@ -0,0 +1,152 @@
+//! Computes features of the series in files, following a pipeline spec.
+
+use std::io::{self, Read};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use catch22::Error;
+use catch22::pipeline::{PipelineSpec, WindowFeatures};
+
+const USAGE: &str = "\
+Usage: catch22 [--spec FILE] [--json] [FILE...]
+
+Computes the features of the series in each FILE, or in standard input if none is given, and
+writes one CSV row per window. Values are separated by whitespace, commas or semicolons; NaN and
+NA mark missing values.
+
+Options:
+  --spec FILE  pipeline spec in TOML, or JSON if FILE ends in .json
+  --json       write one JSON object per window instead of CSV
+  -h, --help   print this help";
+
+struct Args {
+    spec: Option<String>,
+    json: bool,
+    files: Vec<String>,
+}
+
+fn parse_args() -> Result<Option<Args>, String> {
+    let mut args = Args {
+        spec: None,
+        json: false,
+        files: Vec::new(),
+    };
+    let mut it = env::args().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--spec" => args.spec = Some(it.next().ok_or("--spec needs a file")?),
+            "--json" => args.json = true,
+            _ if arg.starts_with('-') && arg != "-" => return Err(format!("unknown option {arg}")),
+            _ => args.files.push(arg),
+        }
+    }
+    Ok(Some(args))
+}
+
+fn load_spec(path: Option<&str>) -> Result<PipelineSpec, String> {
+    let Some(path) = path else {
+        return Ok(PipelineSpec::default());
+    };
+    let text = fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let spec = if path.ends_with(".json") {
+        PipelineSpec::from_json(&text)
+    } else {
+        PipelineSpec::from_toml(&text)
+    };
+    spec.map_err(|e| format!("{path}: {e}"))
+}
+
+fn read_series(path: &str) -> Result<Vec<f64>, String> {
+    let mut text = String::new();
+    if path == "-" {
+        io::stdin().read_to_string(&mut text)
+    } else {
+        fs::File::open(path).and_then(|mut f| f.read_to_string(&mut text))
+    }
+    .map_err(|e| format!("{path}: {e}"))?;
+
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+        .filter(|token| !token.is_empty())
+        .map(|token| match token {
+            "NA" => Ok(f64::NAN),
+            _ => token
+                .parse::<f64>()
+                .map_err(|_| format!("{path}: not a number: {token}")),
+        })
+        .collect()
+}
+
+fn write_csv(file: &str, windows: &[WindowFeatures], header: &mut bool) {
+    for window in windows {
+        if !*header {
+            let names = window.features.names.join(",");
+            println!("file,start,length,{names}");
+            *header = true;
+        }
+        let values = window
+            .features
+            .values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        println!("{file},{},{},{values}", window.start, window.length);
+    }
+}
+
+fn write_json(file: &str, windows: &[WindowFeatures]) -> Result<(), String> {
+    #[derive(serde::Serialize)]
+    struct Row<'a> {
+        file: &'a str,
+        #[serde(flatten)]
+        window: &'a WindowFeatures,
+    }
+
+    for window in windows {
+        let row = serde_json::to_string(&Row { file, window }).map_err(|e| e.to_string())?;
+        println!("{row}");
+    }
+    Ok(())
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let spec = load_spec(args.spec.as_deref())?;
+    let files = if args.files.is_empty() {
+        vec![String::from("-")]
+    } else {
+        args.files
+    };
+
+    let mut header = false;
+    for file in &files {
+        let series = read_series(file)?;
+        let windows = spec
+            .run(&series)
+            .map_err(|e: Error| format!("{file}: {e}"))?;
+        if args.json {
+            write_json(file, &windows)?;
+        } else {
+            write_csv(file, &windows, &mut header);
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = match parse_args() {
+        Ok(Some(args)) => run(args),
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => Err(format!("{e}\n\n{USAGE}")),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("catch22: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
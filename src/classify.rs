@@ -0,0 +1,433 @@
+//! Feature-based time-series classification.
+//!
+//! Two kinds of classifier are provided: nearest neighbours on the features of series, and
+//! decision trees and random forests on extracted [`Features`](crate::Features).
+//!
+//! [`Catch22Classifier`] extracts the features of each training series, standardises them with a
+//! per-feature [`Scaler`] and keeps the scaled vectors. A series is then classified by its `k`
+//! nearest training vectors or by the nearest class centroid.
+//!
+//! Features that a series is too short for, and all features of a series with NaN or infinite
+//! values, are NaN. NaN and infinite feature values are replaced by the training mean after
+//! scaling, so they add nothing to a distance. Features that are constant or never finite in the
+//! training set are ignored.
+//!
+//! With the `serde` feature the classifiers implement `Serialize` and `Deserialize`, and their
+//! `save` and `load` methods store them as JSON.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{Catch22Workspace, Error, FeatureMode, FeatureSet, Options, compute_set_in};
+
+mod tree;
+
+pub use tree::{Criterion, DecisionTree, ForestConfig, RandomForest, TreeConfig};
+
+/// Distance between scaled feature vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Metric {
+    #[default]
+    Euclidean,
+    Manhattan,
+    /// One minus the cosine similarity; 1 when either vector is zero.
+    Cosine,
+}
+
+impl Metric {
+    pub fn distance(self, a: &[f64], b: &[f64]) -> f64 {
+        let pairs = a.iter().zip(b);
+        match self {
+            Metric::Euclidean => pairs.map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt(),
+            Metric::Manhattan => pairs.map(|(x, y)| (x - y).abs()).sum(),
+            Metric::Cosine => {
+                let (ab, aa, bb) = pairs.fold((0.0, 0.0, 0.0), |(ab, aa, bb), (x, y)| {
+                    (ab + x * y, aa + x * x, bb + y * y)
+                });
+                if aa == 0.0 || bb == 0.0 {
+                    1.0
+                } else {
+                    1.0 - ab / (aa.sqrt() * bb.sqrt())
+                }
+            }
+        }
+    }
+}
+
+/// How a series is assigned to a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Method {
+    /// Majority vote of the `k` nearest training series; ties go to the class of the nearest
+    /// of them. The probabilities are the vote shares.
+    Knn { k: usize },
+    /// The class with the nearest mean vector. The probabilities are a softmax of the negative
+    /// distances.
+    NearestCentroid,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassifierConfig {
+    pub method: Method,
+    pub metric: Metric,
+    /// Features the series are compared on.
+    pub features: FeatureSet,
+    pub options: Options,
+}
+
+impl Default for ClassifierConfig {
+    /// 1-NN with the Euclidean distance on the catch22 features, as in the catch22 paper.
+    fn default() -> Self {
+        ClassifierConfig {
+            method: Method::Knn { k: 1 },
+            metric: Metric::Euclidean,
+            features: FeatureMode::Catch22.into(),
+            options: Options::default(),
+        }
+    }
+}
+
+impl ClassifierConfig {
+    pub fn with_knn(mut self, k: usize) -> Self {
+        self.method = Method::Knn { k };
+        self
+    }
+
+    pub fn with_nearest_centroid(mut self) -> Self {
+        self.method = Method::NearestCentroid;
+        self
+    }
+
+    pub fn with_metric(mut self, metric: Metric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    pub fn with_features(mut self, features: FeatureSet) -> Self {
+        self.features = features;
+        self
+    }
+
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Per-feature standardisation fitted on the training vectors.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scaler {
+    /// Mean of the finite values of each feature.
+    pub mean: Vec<f64>,
+    /// Standard deviation of the finite values of each feature; 0 for ignored features.
+    pub std: Vec<f64>,
+}
+
+impl Scaler {
+    /// Fits the scaler on vectors of `n_features` values stored one after the other.
+    pub fn fit(vectors: &[f64], n_features: usize) -> Self {
+        let mut mean = vec![0.0; n_features];
+        let mut std = vec![0.0; n_features];
+
+        for j in 0..n_features {
+            let column = || {
+                vectors[j..]
+                    .iter()
+                    .step_by(n_features)
+                    .filter(|v| v.is_finite())
+            };
+            let n = column().count();
+            if n == 0 {
+                continue;
+            }
+            let m = column().sum::<f64>() / n as f64;
+            let ss = column().map(|v| (v - m) * (v - m)).sum::<f64>();
+            mean[j] = m;
+            std[j] = (ss / n as f64).sqrt();
+        }
+        Scaler { mean, std }
+    }
+
+    /// Standardises `vector` in place, setting non-finite values and ignored features to 0.
+    pub fn transform(&self, vector: &mut [f64]) {
+        for ((v, m), s) in vector.iter_mut().zip(&self.mean).zip(&self.std) {
+            let z = (*v - m) / s;
+            *v = if *s > 0.0 && z.is_finite() { z } else { 0.0 };
+        }
+    }
+}
+
+/// Appends the features of `x` to `out`, with NaN for those the series is too short for and for
+/// all of them if it has non-finite values.
+fn push_features(
+    ws: &mut Catch22Workspace,
+    x: &[f64],
+    config: &ClassifierConfig,
+    out: &mut Vec<f64>,
+) {
+    if x.iter().all(|v| v.is_finite()) {
+        out.extend(compute_set_in(ws, x, &config.features, &config.options).values);
+    } else {
+        out.extend(config.features.iter().map(|_| f64::NAN));
+    }
+}
+
+/// A nearest-neighbour classifier on the features of series.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Catch22Classifier<L> {
+    version: u32,
+    config: ClassifierConfig,
+    classes: Vec<L>,
+    scaler: Scaler,
+    /// Scaled training vectors, one after the other.
+    vectors: Vec<f64>,
+    /// Index in `classes` of each training vector.
+    labels: Vec<usize>,
+    /// Mean scaled vector of each class.
+    centroids: Vec<f64>,
+}
+
+impl<L: Clone + PartialEq> Catch22Classifier<L> {
+    /// Version of the stored form, checked by `load`.
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Fits 1-NN on the catch22 features of `series`.
+    pub fn fit<S: AsRef<[f64]>>(series: &[S], labels: &[L]) -> Result<Self, Error> {
+        Self::fit_with(series, labels, &ClassifierConfig::default())
+    }
+
+    pub fn fit_with<S: AsRef<[f64]>>(
+        series: &[S],
+        labels: &[L],
+        config: &ClassifierConfig,
+    ) -> Result<Self, Error> {
+        if series.len() != labels.len() {
+            return Err(Error::InvalidParameter("one label per series is required"));
+        }
+        if series.is_empty() {
+            return Err(Error::InvalidParameter("no training series"));
+        }
+        if config.method == (Method::Knn { k: 0 }) {
+            return Err(Error::InvalidParameter("k must be at least 1"));
+        }
+        if config.features.is_empty() {
+            return Err(Error::InvalidParameter("no features selected"));
+        }
+
+        let n_features = config.features.len();
+        let mut workspace = Catch22Workspace::new();
+        let mut vectors = Vec::with_capacity(series.len() * n_features);
+        for x in series {
+            push_features(&mut workspace, x.as_ref(), config, &mut vectors);
+        }
+
+        let mut classes = Vec::new();
+        let labels = labels
+            .iter()
+            .map(|label| match classes.iter().position(|c| c == label) {
+                Some(i) => i,
+                None => {
+                    classes.push(label.clone());
+                    classes.len() - 1
+                }
+            })
+            .collect::<Vec<usize>>();
+
+        let scaler = Scaler::fit(&vectors, n_features);
+        for vector in vectors.chunks_exact_mut(n_features) {
+            scaler.transform(vector);
+        }
+
+        let mut centroids = vec![0.0; classes.len() * n_features];
+        let mut counts = vec![0usize; classes.len()];
+        for (vector, &label) in vectors.chunks_exact(n_features).zip(&labels) {
+            let centroid = &mut centroids[label * n_features..(label + 1) * n_features];
+            centroid.iter_mut().zip(vector).for_each(|(c, v)| *c += v);
+            counts[label] += 1;
+        }
+        for (centroid, &count) in centroids.chunks_exact_mut(n_features).zip(&counts) {
+            centroid.iter_mut().for_each(|c| *c /= count as f64);
+        }
+
+        Ok(Catch22Classifier {
+            version: Self::FORMAT_VERSION,
+            config: config.clone(),
+            classes,
+            scaler,
+            vectors,
+            labels,
+            centroids,
+        })
+    }
+
+    pub fn config(&self) -> &ClassifierConfig {
+        &self.config
+    }
+
+    /// The classes in order of first appearance in the training labels, as indexed by
+    /// [`predict_proba`](Catch22Classifier::predict_proba).
+    pub fn classes(&self) -> &[L] {
+        &self.classes
+    }
+
+    pub fn scaler(&self) -> &Scaler {
+        &self.scaler
+    }
+
+    /// The scaled feature vector of `x`.
+    pub fn transform(&self, x: &[f64]) -> Vec<f64> {
+        let mut vector = Vec::with_capacity(self.config.features.len());
+        push_features(&mut Catch22Workspace::new(), x, &self.config, &mut vector);
+        self.scaler.transform(&mut vector);
+        vector
+    }
+
+    pub fn predict(&self, x: &[f64]) -> L {
+        let vector = self.transform(x);
+        let class = match self.config.method {
+            Method::Knn { k } => {
+                let neighbours = self.neighbours(&vector, k);
+                let votes = self.votes(&neighbours);
+                let most = votes.iter().copied().max().unwrap_or(0);
+                // the nearest neighbour among the classes with the most votes
+                neighbours
+                    .iter()
+                    .map(|&(_, label)| label)
+                    .find(|&label| votes[label] == most)
+                    .unwrap_or(0)
+            }
+            Method::NearestCentroid => {
+                let distances = self.centroid_distances(&vector);
+                (0..distances.len())
+                    .min_by(|&i, &j| distances[i].total_cmp(&distances[j]))
+                    .unwrap_or(0)
+            }
+        };
+        self.classes[class].clone()
+    }
+
+    /// The probability of each class of [`classes`](Catch22Classifier::classes).
+    pub fn predict_proba(&self, x: &[f64]) -> Vec<f64> {
+        let vector = self.transform(x);
+        match self.config.method {
+            Method::Knn { k } => {
+                let neighbours = self.neighbours(&vector, k);
+                let votes = self.votes(&neighbours);
+                votes
+                    .iter()
+                    .map(|&v| v as f64 / neighbours.len() as f64)
+                    .collect()
+            }
+            Method::NearestCentroid => {
+                let distances = self.centroid_distances(&vector);
+                let nearest = distances.iter().copied().fold(f64::INFINITY, f64::min);
+                let weights = distances
+                    .iter()
+                    .map(|d| (nearest - d).exp())
+                    .collect::<Vec<f64>>();
+                let total = weights.iter().sum::<f64>();
+                weights.iter().map(|w| w / total).collect()
+            }
+        }
+    }
+
+    /// The distance and class of the `k` nearest training vectors, nearest first.
+    fn neighbours(&self, vector: &[f64], k: usize) -> Vec<(f64, usize)> {
+        let n_features = vector.len();
+        let mut neighbours = self
+            .vectors
+            .chunks_exact(n_features)
+            .zip(&self.labels)
+            .map(|(v, &label)| (self.config.metric.distance(vector, v), label))
+            .collect::<Vec<_>>();
+        // stable, so equidistant series keep their training order
+        neighbours.sort_by(|a, b| a.0.total_cmp(&b.0));
+        neighbours.truncate(k);
+        neighbours
+    }
+
+    fn votes(&self, neighbours: &[(f64, usize)]) -> Vec<usize> {
+        let mut votes = vec![0; self.classes.len()];
+        for &(_, label) in neighbours {
+            votes[label] += 1;
+        }
+        votes
+    }
+
+    fn centroid_distances(&self, vector: &[f64]) -> Vec<f64> {
+        self.centroids
+            .chunks_exact(vector.len())
+            .map(|c| self.config.metric.distance(vector, c))
+            .collect()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<L> Catch22Classifier<L>
+where
+    L: Clone + PartialEq + serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// The classifier as JSON.
+    pub fn save(&self) -> alloc::string::String {
+        serde_json::to_string(self).expect("a classifier serializes to JSON")
+    }
+
+    /// A classifier stored by [`save`](Catch22Classifier::save).
+    pub fn load(json: &str) -> Result<Self, Error> {
+        let classifier: Self = load_model(json, |c: &Self| c.version, Self::FORMAT_VERSION)?;
+        classifier.check()?;
+        Ok(classifier)
+    }
+
+    /// Checks that the parts of a loaded classifier fit together.
+    fn check(&self) -> Result<(), Error> {
+        let n_features = self.config.features.len();
+        let invalid = |what: &str| Err(Error::InvalidModel(alloc::format!("inconsistent {what}")));
+        if self.classes.is_empty() || self.labels.is_empty() {
+            return invalid("classes");
+        }
+        if n_features == 0 || self.config.method == (Method::Knn { k: 0 }) {
+            return invalid("config");
+        }
+        if self.labels.iter().any(|&label| label >= self.classes.len()) {
+            return invalid("labels");
+        }
+        if self.vectors.len() != self.labels.len() * n_features {
+            return invalid("vectors");
+        }
+        if self.scaler.mean.len() != n_features || self.scaler.std.len() != n_features {
+            return invalid("scaler");
+        }
+        if self.centroids.len() != self.classes.len() * n_features {
+            return invalid("centroids");
+        }
+        Ok(())
+    }
+}
+
+/// Parses a model stored as JSON, checking its format version.
+#[cfg(feature = "serde")]
+fn load_model<T: serde::de::DeserializeOwned>(
+    json: &str,
+    version: impl Fn(&T) -> u32,
+    expected: u32,
+) -> Result<T, Error> {
+    use alloc::string::ToString;
+
+    let model: T = serde_json::from_str(json).map_err(|e| Error::InvalidModel(e.to_string()))?;
+    if version(&model) != expected {
+        return Err(Error::InvalidModel(alloc::format!(
+            "unsupported version {}, expected {expected}",
+            version(&model)
+        )));
+    }
+    Ok(model)
+}
@@ -0,0 +1,750 @@
+//! CART decision trees and random forests on extracted features.
+//!
+//! Splits are binary thresholds on one feature, chosen to minimise the Gini impurity or the
+//! entropy of the children weighted by their sizes. NaN values compare greater than every
+//! threshold, both when training and when predicting.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use super::load_model;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::{Error, Features};
+
+/// Impurity measure of the class distribution in a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Criterion {
+    #[default]
+    Gini,
+    /// Shannon entropy in bits.
+    Entropy,
+}
+
+impl Criterion {
+    fn impurity(self, counts: &[usize], n: usize) -> f64 {
+        let p = counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| c as f64 / n as f64);
+        match self {
+            Criterion::Gini => 1.0 - p.map(|p| p * p).sum::<f64>(),
+            Criterion::Entropy => -p.map(|p| p * p.log2()).sum::<f64>(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TreeConfig {
+    pub criterion: Criterion,
+    /// Depth limit; the root has depth 0.
+    pub max_depth: Option<usize>,
+    /// Smallest node that is split.
+    pub min_samples_split: usize,
+    /// Smallest child a split may leave.
+    pub min_samples_leaf: usize,
+    /// Number of features drawn at random as candidates for each split; all of them if `None`,
+    /// except in a [`RandomForest`], which then draws the square root of their number.
+    pub max_features: Option<usize>,
+    /// Seed of the feature draws and of the bootstrap samples of a forest.
+    pub seed: u64,
+}
+
+impl Default for TreeConfig {
+    fn default() -> Self {
+        TreeConfig {
+            criterion: Criterion::Gini,
+            max_depth: None,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+            max_features: None,
+            seed: 0,
+        }
+    }
+}
+
+impl TreeConfig {
+    pub fn with_criterion(mut self, criterion: Criterion) -> Self {
+        self.criterion = criterion;
+        self
+    }
+
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    pub fn with_min_samples_split(mut self, n: usize) -> Self {
+        self.min_samples_split = n;
+        self
+    }
+
+    pub fn with_min_samples_leaf(mut self, n: usize) -> Self {
+        self.min_samples_leaf = n;
+        self
+    }
+
+    pub fn with_max_features(mut self, n: usize) -> Self {
+        self.max_features = Some(n);
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForestConfig {
+    pub n_trees: usize,
+    /// Settings of every tree. Its seed drives the whole forest.
+    pub tree: TreeConfig,
+}
+
+impl Default for ForestConfig {
+    fn default() -> Self {
+        ForestConfig {
+            n_trees: 100,
+            tree: TreeConfig::default(),
+        }
+    }
+}
+
+impl ForestConfig {
+    pub fn with_n_trees(mut self, n: usize) -> Self {
+        self.n_trees = n;
+        self
+    }
+
+    pub fn with_tree(mut self, tree: TreeConfig) -> Self {
+        self.tree = tree;
+        self
+    }
+}
+
+/// SplitMix64, so that models are reproducible from their seed on every platform.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// Feature vectors and class indices of the training set.
+struct Dataset<L> {
+    names: Vec<String>,
+    classes: Vec<L>,
+    /// Vectors of `names.len()` values, one after the other.
+    x: Vec<f64>,
+    y: Vec<usize>,
+}
+
+impl<L: Clone + PartialEq> Dataset<L> {
+    fn new(features: &[Features], labels: &[L]) -> Result<Self, Error> {
+        if features.len() != labels.len() {
+            return Err(Error::InvalidParameter(
+                "one label per feature vector is required",
+            ));
+        }
+        let Some(first) = features.first() else {
+            return Err(Error::InvalidParameter("no training vectors"));
+        };
+        if first.is_empty() {
+            return Err(Error::InvalidParameter("no features"));
+        }
+        if features.iter().any(|f| f.names != first.names) {
+            return Err(Error::InvalidParameter(
+                "every feature vector must have the same features",
+            ));
+        }
+
+        let mut classes = Vec::new();
+        let y = labels
+            .iter()
+            .map(|label| match classes.iter().position(|c| c == label) {
+                Some(i) => i,
+                None => {
+                    classes.push(label.clone());
+                    classes.len() - 1
+                }
+            })
+            .collect();
+
+        Ok(Dataset {
+            names: first.names.clone(),
+            classes,
+            x: features
+                .iter()
+                .flat_map(|f| f.values.iter().copied())
+                .collect(),
+            y,
+        })
+    }
+}
+
+impl<L> Dataset<L> {
+    fn n_features(&self) -> usize {
+        self.names.len()
+    }
+}
+
+/// The values of `features` in the order of `names`, NaN for those it lacks.
+fn row(names: &[String], features: &Features) -> Vec<f64> {
+    if features.names == names {
+        return features.values.clone();
+    }
+    names
+        .iter()
+        .map(|name| features.get(name).unwrap_or(f64::NAN))
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+enum Node {
+    /// Class probabilities of the training vectors that reached the leaf.
+    Leaf { proba: Vec<f64> },
+    /// Vectors whose feature is at most the threshold go left, the others right.
+    Split {
+        feature: usize,
+        threshold: f64,
+        left: usize,
+        right: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Tree {
+    /// The root is the first node.
+    nodes: Vec<Node>,
+    /// Total impurity decrease of the splits on each feature, summing to 1 unless the tree is a
+    /// single leaf.
+    importances: Vec<f64>,
+}
+
+impl Tree {
+    fn proba(&self, x: &[f64]) -> &[f64] {
+        let mut i = 0;
+        loop {
+            match &self.nodes[i] {
+                Node::Leaf { proba } => return proba,
+                Node::Split {
+                    feature,
+                    threshold,
+                    left,
+                    right,
+                } => {
+                    i = if x[*feature] <= *threshold {
+                        *left
+                    } else {
+                        *right
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks that a loaded tree has its children after their parents and indices in range,
+    /// so that [`proba`](Tree::proba) ends.
+    #[cfg(feature = "serde")]
+    fn check(&self, n_features: usize, n_classes: usize) -> Result<(), Error> {
+        let invalid = |what: &str| Err(Error::InvalidModel(alloc::format!("inconsistent {what}")));
+        if self.nodes.is_empty() || n_classes == 0 || self.importances.len() != n_features {
+            return invalid("tree");
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            match node {
+                Node::Leaf { proba } if proba.len() != n_classes => return invalid("leaf"),
+                Node::Leaf { .. } => {}
+                Node::Split {
+                    feature,
+                    left,
+                    right,
+                    ..
+                } => {
+                    let child = |c: usize| i < c && c < self.nodes.len();
+                    if *feature >= n_features || !child(*left) || !child(*right) {
+                        return invalid("split");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn depth(&self, i: usize) -> usize {
+        match &self.nodes[i] {
+            Node::Leaf { .. } => 0,
+            Node::Split { left, right, .. } => 1 + self.depth(*left).max(self.depth(*right)),
+        }
+    }
+}
+
+struct Builder<'a, L> {
+    data: &'a Dataset<L>,
+    config: &'a TreeConfig,
+    max_features: usize,
+    rng: Rng,
+    nodes: Vec<Node>,
+    importances: Vec<f64>,
+    /// Candidate features, shuffled for each split.
+    features: Vec<usize>,
+    /// Samples of the node being split, sorted by a feature.
+    sorted: Vec<usize>,
+}
+
+/// The best split of a node.
+struct Split {
+    feature: usize,
+    threshold: f64,
+    decrease: f64,
+}
+
+impl<L> Builder<'_, L> {
+    fn fit(
+        data: &Dataset<L>,
+        config: &TreeConfig,
+        max_features: usize,
+        samples: &mut [usize],
+    ) -> Tree {
+        let n_features = data.n_features();
+        let mut builder = Builder {
+            data,
+            config,
+            max_features: max_features.clamp(1, n_features),
+            rng: Rng(config.seed),
+            nodes: Vec::new(),
+            importances: vec![0.0; n_features],
+            features: (0..n_features).collect(),
+            sorted: Vec::with_capacity(samples.len()),
+        };
+        builder.grow(samples, 0);
+
+        let total = builder.importances.iter().sum::<f64>();
+        if total > 0.0 {
+            builder.importances.iter_mut().for_each(|v| *v /= total);
+        }
+        Tree {
+            nodes: builder.nodes,
+            importances: builder.importances,
+        }
+    }
+
+    fn value(&self, sample: usize, feature: usize) -> f64 {
+        self.data.x[sample * self.data.n_features() + feature]
+    }
+
+    fn counts(&self, samples: &[usize]) -> Vec<usize> {
+        let mut counts = vec![0; self.data.classes.len()];
+        samples.iter().for_each(|&s| counts[self.data.y[s]] += 1);
+        counts
+    }
+
+    /// Grows the subtree of `samples`, returning the index of its root.
+    fn grow(&mut self, samples: &mut [usize], depth: usize) -> usize {
+        let index = self.nodes.len();
+        let counts = self.counts(samples);
+        let n = samples.len();
+        self.nodes.push(Node::Leaf {
+            proba: counts.iter().map(|&c| c as f64 / n as f64).collect(),
+        });
+
+        let impurity = self.config.criterion.impurity(&counts, n);
+        if impurity == 0.0
+            || n < self.config.min_samples_split
+            || self.config.max_depth.is_some_and(|d| depth >= d)
+        {
+            return index;
+        }
+        let Some(split) = self.best_split(samples, &counts, impurity) else {
+            return index;
+        };
+
+        self.importances[split.feature] += split.decrease;
+        let mut n_left = 0;
+        for i in 0..n {
+            if self.value(samples[i], split.feature) <= split.threshold {
+                samples.swap(i, n_left);
+                n_left += 1;
+            }
+        }
+        let (left, right) = samples.split_at_mut(n_left);
+        let left = self.grow(left, depth + 1);
+        let right = self.grow(right, depth + 1);
+        self.nodes[index] = Node::Split {
+            feature: split.feature,
+            threshold: split.threshold,
+            left,
+            right,
+        };
+        index
+    }
+
+    fn best_split(&mut self, samples: &[usize], counts: &[usize], impurity: f64) -> Option<Split> {
+        let n = samples.len();
+        let min_leaf = self.config.min_samples_leaf.max(1);
+        let criterion = self.config.criterion;
+        let mut best: Option<Split> = None;
+
+        // a partial Fisher–Yates shuffle draws the candidate features
+        let n_features = self.features.len();
+        for k in 0..self.max_features {
+            let j = k + self.rng.below(n_features - k);
+            self.features.swap(k, j);
+        }
+
+        for k in 0..self.max_features {
+            let feature = self.features[k];
+            let mut sorted = core::mem::take(&mut self.sorted);
+            sorted.clear();
+            sorted.extend_from_slice(samples);
+            sorted.sort_by(|&a, &b| {
+                let (va, vb) = (self.value(a, feature), self.value(b, feature));
+                va.is_nan().cmp(&vb.is_nan()).then(va.total_cmp(&vb))
+            });
+            let n_values = sorted
+                .iter()
+                .take_while(|&&s| !self.value(s, feature).is_nan())
+                .count();
+
+            let mut left = vec![0; counts.len()];
+            let mut right = counts.to_vec();
+            // the last candidate sends the values left and NaN right
+            for i in 0..n_values {
+                let class = self.data.y[sorted[i]];
+                left[class] += 1;
+                right[class] -= 1;
+
+                let a = self.value(sorted[i], feature);
+                let b = sorted
+                    .get(i + 1)
+                    .map_or(f64::NAN, |&s| self.value(s, feature));
+                let (n_left, n_right) = (i + 1, n - i - 1);
+                if a == b || n_left < min_leaf || n_right < min_leaf {
+                    continue;
+                }
+
+                let decrease = n as f64 * impurity
+                    - n_left as f64 * criterion.impurity(&left, n_left)
+                    - n_right as f64 * criterion.impurity(&right, n_right);
+                if decrease <= best.as_ref().map_or(0.0, |s| s.decrease) {
+                    continue;
+                }
+                // NaN when b is, or when the values are infinite
+                let mid = a + (b - a) / 2.0;
+                let threshold = if mid < b { mid } else { a };
+                // JSON has no infinities, so −∞ becomes the lowest finite value and splits
+                // between +∞ and NaN are not made
+                let threshold = threshold.max(f64::MIN);
+                if threshold.is_finite() && (threshold < b || b.is_nan()) {
+                    best = Some(Split {
+                        feature,
+                        threshold,
+                        decrease,
+                    });
+                }
+            }
+            self.sorted = sorted;
+        }
+        best
+    }
+}
+
+/// The index of the most probable class, the first of equally probable ones.
+fn most_probable(proba: &[f64]) -> usize {
+    let mut best = 0;
+    for (i, &p) in proba.iter().enumerate() {
+        if p > proba[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+/// A CART decision tree.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecisionTree<L> {
+    version: u32,
+    config: TreeConfig,
+    names: Vec<String>,
+    classes: Vec<L>,
+    tree: Tree,
+}
+
+impl<L: Clone + PartialEq> DecisionTree<L> {
+    /// Version of the stored form, checked by `load`.
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Fits a tree with the default settings on feature vectors such as those of
+    /// [`compute_all`](crate::compute_all).
+    pub fn fit(features: &[Features], labels: &[L]) -> Result<Self, Error> {
+        Self::fit_with(features, labels, &TreeConfig::default())
+    }
+
+    pub fn fit_with(
+        features: &[Features],
+        labels: &[L],
+        config: &TreeConfig,
+    ) -> Result<Self, Error> {
+        let data = Dataset::new(features, labels)?;
+        let max_features = config.max_features.unwrap_or(data.n_features());
+        let mut samples = (0..data.y.len()).collect::<Vec<usize>>();
+        let tree = Builder::fit(&data, config, max_features, &mut samples);
+
+        Ok(DecisionTree {
+            version: Self::FORMAT_VERSION,
+            config: *config,
+            names: data.names,
+            classes: data.classes,
+            tree,
+        })
+    }
+
+    pub fn config(&self) -> &TreeConfig {
+        &self.config
+    }
+
+    /// The classes in order of first appearance in the training labels, as indexed by
+    /// [`predict_proba`](DecisionTree::predict_proba).
+    pub fn classes(&self) -> &[L] {
+        &self.classes
+    }
+
+    pub fn feature_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The share of the impurity decrease of each feature, by name.
+    pub fn feature_importances(&self) -> Vec<(&str, f64)> {
+        self.names
+            .iter()
+            .map(|n| n.as_str())
+            .zip(self.tree.importances.iter().copied())
+            .collect()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.tree.depth(0)
+    }
+
+    pub fn n_leaves(&self) -> usize {
+        self.tree
+            .nodes
+            .iter()
+            .filter(|n| matches!(n, Node::Leaf { .. }))
+            .count()
+    }
+
+    /// The class of `features`, matched to the training features by name.
+    pub fn predict(&self, features: &Features) -> L {
+        self.classes[most_probable(&self.predict_proba(features))].clone()
+    }
+
+    /// The probability of each class of [`classes`](DecisionTree::classes): the class shares of
+    /// the training vectors in the leaf reached.
+    pub fn predict_proba(&self, features: &Features) -> Vec<f64> {
+        self.tree.proba(&row(&self.names, features)).to_vec()
+    }
+}
+
+/// A random forest of CART trees, each fitted on a bootstrap sample of the training vectors.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RandomForest<L> {
+    version: u32,
+    config: ForestConfig,
+    names: Vec<String>,
+    classes: Vec<L>,
+    trees: Vec<Tree>,
+    oob_error: Option<f64>,
+}
+
+impl<L: Clone + PartialEq> RandomForest<L> {
+    /// Version of the stored form, checked by `load`.
+    pub const FORMAT_VERSION: u32 = 1;
+
+    /// Fits a forest with the default settings on feature vectors such as those of
+    /// [`compute_all`](crate::compute_all).
+    pub fn fit(features: &[Features], labels: &[L]) -> Result<Self, Error> {
+        Self::fit_with(features, labels, &ForestConfig::default())
+    }
+
+    pub fn fit_with(
+        features: &[Features],
+        labels: &[L],
+        config: &ForestConfig,
+    ) -> Result<Self, Error> {
+        if config.n_trees == 0 {
+            return Err(Error::InvalidParameter("a forest needs at least one tree"));
+        }
+        let data = Dataset::new(features, labels)?;
+        let n = data.y.len();
+        let max_features = config
+            .tree
+            .max_features
+            .unwrap_or_else(|| (data.n_features() as f64).sqrt().round() as usize);
+
+        let mut rng = Rng(config.tree.seed);
+        let mut trees = Vec::with_capacity(config.n_trees);
+        let mut samples = vec![0; n];
+        let mut in_bag = vec![false; n];
+        // summed out-of-bag probabilities of each training vector
+        let mut oob = vec![0.0; n * data.classes.len()];
+        let mut n_oob = vec![0usize; n];
+
+        for _ in 0..config.n_trees {
+            in_bag.fill(false);
+            for s in samples.iter_mut() {
+                *s = rng.below(n);
+                in_bag[*s] = true;
+            }
+            let tree_config = config.tree.with_seed(rng.next_u64());
+            let tree = Builder::fit(&data, &tree_config, max_features, &mut samples);
+
+            let n_features = data.n_features();
+            for i in (0..n).filter(|&i| !in_bag[i]) {
+                let proba = tree.proba(&data.x[i * n_features..(i + 1) * n_features]);
+                let sum = &mut oob[i * proba.len()..(i + 1) * proba.len()];
+                sum.iter_mut().zip(proba).for_each(|(s, p)| *s += p);
+                n_oob[i] += 1;
+            }
+            trees.push(tree);
+        }
+
+        let n_classes = data.classes.len();
+        let scored = (0..n).filter(|&i| n_oob[i] > 0);
+        let (errors, total) = scored.fold((0, 0), |(errors, total), i| {
+            let predicted = most_probable(&oob[i * n_classes..(i + 1) * n_classes]);
+            (errors + usize::from(predicted != data.y[i]), total + 1)
+        });
+        let oob_error = (total > 0).then(|| errors as f64 / total as f64);
+
+        Ok(RandomForest {
+            version: Self::FORMAT_VERSION,
+            config: *config,
+            names: data.names,
+            classes: data.classes,
+            trees,
+            oob_error,
+        })
+    }
+
+    pub fn config(&self) -> &ForestConfig {
+        &self.config
+    }
+
+    /// The classes in order of first appearance in the training labels, as indexed by
+    /// [`predict_proba`](RandomForest::predict_proba).
+    pub fn classes(&self) -> &[L] {
+        &self.classes
+    }
+
+    pub fn feature_names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The impurity-decrease importance of each feature, by name, averaged over the trees.
+    pub fn feature_importances(&self) -> Vec<(&str, f64)> {
+        let mut importances = vec![0.0; self.names.len()];
+        for tree in &self.trees {
+            importances
+                .iter_mut()
+                .zip(&tree.importances)
+                .for_each(|(sum, v)| *sum += v);
+        }
+        self.names
+            .iter()
+            .map(|n| n.as_str())
+            .zip(importances.iter().map(|v| v / self.trees.len() as f64))
+            .collect()
+    }
+
+    /// The share of training vectors misclassified by the trees that did not see them; `None` if
+    /// every vector was in every bootstrap sample.
+    pub fn oob_error(&self) -> Option<f64> {
+        self.oob_error
+    }
+
+    /// The class of `features`, matched to the training features by name.
+    pub fn predict(&self, features: &Features) -> L {
+        self.classes[most_probable(&self.predict_proba(features))].clone()
+    }
+
+    /// The probability of each class of [`classes`](RandomForest::classes), averaged over the
+    /// trees.
+    pub fn predict_proba(&self, features: &Features) -> Vec<f64> {
+        let x = row(&self.names, features);
+        let mut proba = vec![0.0; self.classes.len()];
+        for tree in &self.trees {
+            proba
+                .iter_mut()
+                .zip(tree.proba(&x))
+                .for_each(|(sum, p)| *sum += p);
+        }
+        proba.iter_mut().for_each(|p| *p /= self.trees.len() as f64);
+        proba
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<L> DecisionTree<L>
+where
+    L: Clone + PartialEq + serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// The tree as JSON.
+    pub fn save(&self) -> String {
+        serde_json::to_string(self).expect("a tree serializes to JSON")
+    }
+
+    /// A tree stored by [`save`](DecisionTree::save).
+    pub fn load(json: &str) -> Result<Self, Error> {
+        let tree: Self = load_model(json, |t: &Self| t.version, Self::FORMAT_VERSION)?;
+        tree.tree.check(tree.names.len(), tree.classes.len())?;
+        Ok(tree)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<L> RandomForest<L>
+where
+    L: Clone + PartialEq + serde::Serialize + serde::de::DeserializeOwned,
+{
+    /// The forest as JSON.
+    pub fn save(&self) -> String {
+        serde_json::to_string(self).expect("a forest serializes to JSON")
+    }
+
+    /// A forest stored by [`save`](RandomForest::save).
+    pub fn load(json: &str) -> Result<Self, Error> {
+        let forest: Self = load_model(json, |f: &Self| f.version, Self::FORMAT_VERSION)?;
+        if forest.trees.is_empty() {
+            return Err(Error::InvalidModel(
+                "a forest needs at least one tree".into(),
+            ));
+        }
+        for tree in &forest.trees {
+            tree.check(forest.names.len(), forest.classes.len())?;
+        }
+        Ok(forest)
+    }
+}
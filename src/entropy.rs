@@ -0,0 +1,139 @@
+//! Entropy measures: sample, approximate, permutation and multiscale entropy.
+//!
+//! Template matching uses the Chebyshev distance. Templates are sorted by their first value so
+//! that each one is only compared with the templates whose first value lies within `r`, which
+//! avoids the all-pairs comparison unless the series is nearly constant at the scale of `r`.
+//!
+//! Invalid parameters (or series too short for them) give `NaN`, and so do series with `NaN`
+//! values in the template-matching entropies.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+/// Counts the pairs of distinct templates of length `dim`, among the first `n_templates`, whose
+/// Chebyshev distance is at most `r`. If given, `counts[i]` is incremented for every match of
+/// template `i`.
+fn neighbour_pairs(
+    a: &[f64],
+    dim: usize,
+    n_templates: usize,
+    r: f64,
+    mut counts: Option<&mut [usize]>,
+) -> usize {
+    let mut order = (0..n_templates).collect::<Vec<usize>>();
+    order.sort_unstable_by(|&i, &j| a[i].total_cmp(&a[j]));
+
+    let mut pairs = 0;
+    for p in 0..n_templates {
+        let i = order[p];
+        for q in p + 1..n_templates {
+            let j = order[q];
+            if a[j] - a[i] > r {
+                break;
+            }
+            if (1..dim).all(|k| (a[i + k] - a[j + k]).abs() <= r) {
+                pairs += 1;
+                if let Some(counts) = counts.as_deref_mut() {
+                    counts[i] += 1;
+                    counts[j] += 1;
+                }
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Sample entropy (Richman & Moorman, 2000) with embedding dimension `m` and absolute
+/// tolerance `r`, usually `0.2` times the standard deviation of the series.
+pub fn sample_entropy(a: &[f64], m: usize, r: f64) -> f64 {
+    if m == 0 || a.len() <= m + 1 || a.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let n_templates = a.len() - m;
+
+    let b = neighbour_pairs(a, m, n_templates, r, None);
+    let c = neighbour_pairs(a, m + 1, n_templates, r, None);
+
+    return -(c as f64 / b as f64).ln();
+}
+
+fn phi(a: &[f64], dim: usize, r: f64) -> f64 {
+    let n_templates = a.len() - dim + 1;
+    // every template matches itself
+    let mut counts = vec![1; n_templates];
+    neighbour_pairs(a, dim, n_templates, r, Some(&mut counts));
+
+    counts
+        .iter()
+        .map(|&c| (c as f64 / n_templates as f64).ln())
+        .sum::<f64>()
+        / n_templates as f64
+}
+
+/// Approximate entropy (Pincus, 1991) with embedding dimension `m` and absolute tolerance `r`.
+pub fn approximate_entropy(a: &[f64], m: usize, r: f64) -> f64 {
+    if m == 0 || a.len() <= m + 1 || a.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    return phi(a, m, r) - phi(a, m + 1, r);
+}
+
+/// Shannon entropy (in nats) of the ordinal patterns of `order` values spaced `delay` samples
+/// apart (Bandt & Pompe, 2002). Ties are ranked by position. Divide by `ln(order!)` to normalise
+/// to [0, 1]. Orders above 10 are not supported.
+pub fn permutation_entropy(a: &[f64], order: usize, delay: usize) -> f64 {
+    if !(2..=10).contains(&order) || delay == 0 || a.len() <= (order - 1) * delay {
+        return f64::NAN;
+    }
+
+    let mut factorial = vec![1; order];
+    for k in 1..order {
+        factorial[k] = factorial[k - 1] * k;
+    }
+
+    let n_patterns = a.len() - (order - 1) * delay;
+    let mut counts = vec![0usize; factorial[order - 1] * order];
+
+    for i in 0..n_patterns {
+        // Lehmer code of the pattern
+        let mut code = 0;
+        for k in 0..order {
+            let v = a[i + k * delay];
+            let smaller = (k + 1..order).filter(|&j| a[i + j * delay] < v).count();
+            code += smaller * factorial[order - 1 - k];
+        }
+        counts[code] += 1;
+    }
+
+    let mut h = 0.0;
+    for &c in &counts {
+        if c > 0 {
+            let p = c as f64 / n_patterns as f64;
+            h -= p * p.ln();
+        }
+    }
+    return h;
+}
+
+/// Means of consecutive non-overlapping windows of `scale` samples.
+pub fn coarse_grain(a: &[f64], scale: usize) -> Vec<f64> {
+    if scale == 0 {
+        return Vec::new();
+    }
+    a.chunks_exact(scale)
+        .map(|w| w.iter().sum::<f64>() / scale as f64)
+        .collect()
+}
+
+/// Sample entropy of the coarse-grained series at scales `1..=max_scale` (Costa et al., 2002).
+/// The tolerance `r` is absolute and shared by all scales, normally derived from the standard
+/// deviation of the original series.
+pub fn multiscale_entropy(a: &[f64], m: usize, r: f64, max_scale: usize) -> Vec<f64> {
+    (1..=max_scale)
+        .map(|scale| sample_entropy(&coarse_grain(a, scale), m, r))
+        .collect()
+}
@@ -0,0 +1,55 @@
+use alloc::string::String;
+use core::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The series is shorter than the analysis requires.
+    TooShort { len: usize, min: usize },
+    /// The analysis is undefined for a constant series.
+    ConstantSeries,
+    /// Too few distinct scales are available to fit the scaling regions.
+    NotEnoughScales { found: usize, required: usize },
+    /// A configuration value is outside its valid range.
+    InvalidParameter(&'static str),
+    /// A linear system is singular or too ill-conditioned to solve.
+    SingularSystem,
+    /// The analysis expects a z-scored series.
+    NotNormalized,
+    /// No feature has the given name or index.
+    UnknownFeature(String),
+    /// The series contains NaN values and the analysis does not accept them.
+    MissingValues,
+    /// A pipeline specification could not be parsed.
+    InvalidSpec(String),
+    /// A stored model could not be loaded.
+    InvalidModel(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::TooShort { len, min } => {
+                write!(
+                    f,
+                    "series of length {len} is too short, at least {min} required"
+                )
+            }
+            Error::ConstantSeries => write!(f, "series is constant"),
+            Error::NotEnoughScales { found, required } => {
+                write!(
+                    f,
+                    "{found} distinct scales available, at least {required} required"
+                )
+            }
+            Error::InvalidParameter(what) => write!(f, "invalid parameter: {what}"),
+            Error::SingularSystem => write!(f, "linear system is singular"),
+            Error::NotNormalized => write!(f, "series is not z-scored"),
+            Error::UnknownFeature(name) => write!(f, "unknown feature: {name}"),
+            Error::MissingValues => write!(f, "series contains missing values"),
+            Error::InvalidSpec(message) => write!(f, "invalid pipeline spec: {message}"),
+            Error::InvalidModel(message) => write!(f, "invalid model: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
@@ -0,0 +1,129 @@
+//! Sets of features to compute together.
+
+use alloc::string::ToString;
+use core::ops::{BitOr, BitOrAssign};
+
+use crate::{Error, Feature, FeatureMode};
+
+/// A set of [`Feature`]s, stored as a bitset in the order of
+/// [`FEATURE_NAMES`](crate::FEATURE_NAMES).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FeatureSet {
+    bits: u32,
+}
+
+impl FeatureSet {
+    pub const fn empty() -> Self {
+        FeatureSet { bits: 0 }
+    }
+
+    pub const fn all() -> Self {
+        FeatureSet {
+            bits: (1 << Feature::ALL.len()) - 1,
+        }
+    }
+
+    /// The features with the given long or short names.
+    pub fn from_names<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Self, Error> {
+        names
+            .into_iter()
+            .map(|name| Feature::from_name(name).ok_or_else(|| Error::UnknownFeature(name.into())))
+            .collect()
+    }
+
+    /// The features at the given indices of [`FEATURE_NAMES`](crate::FEATURE_NAMES).
+    pub fn from_indices(indices: impl IntoIterator<Item = usize>) -> Result<Self, Error> {
+        indices
+            .into_iter()
+            .map(|i| Feature::from_index(i).ok_or_else(|| Error::UnknownFeature(i.to_string())))
+            .collect()
+    }
+
+    /// The features tagged with `keyword`.
+    pub fn from_keyword(keyword: &str) -> Self {
+        Feature::ALL
+            .into_iter()
+            .filter(|f| f.info().has_keyword(keyword))
+            .collect()
+    }
+
+    /// Adds `feature`, returning whether it was absent.
+    pub fn insert(&mut self, feature: Feature) -> bool {
+        let absent = !self.contains(feature);
+        self.bits |= 1 << feature.index();
+        absent
+    }
+
+    /// Removes `feature`, returning whether it was present.
+    pub fn remove(&mut self, feature: Feature) -> bool {
+        let present = self.contains(feature);
+        self.bits &= !(1 << feature.index());
+        present
+    }
+
+    pub fn contains(&self, feature: Feature) -> bool {
+        self.bits & (1 << feature.index()) != 0
+    }
+
+    pub fn with(mut self, feature: Feature) -> Self {
+        self.insert(feature);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// The features in the order of [`FEATURE_NAMES`](crate::FEATURE_NAMES).
+    pub fn iter(&self) -> impl Iterator<Item = Feature> + '_ {
+        Feature::ALL.into_iter().filter(|&f| self.contains(f))
+    }
+}
+
+impl FromIterator<Feature> for FeatureSet {
+    fn from_iter<I: IntoIterator<Item = Feature>>(iter: I) -> Self {
+        let mut set = FeatureSet::empty();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Feature> for FeatureSet {
+    fn extend<I: IntoIterator<Item = Feature>>(&mut self, iter: I) {
+        for feature in iter {
+            self.insert(feature);
+        }
+    }
+}
+
+impl BitOr for FeatureSet {
+    type Output = FeatureSet;
+
+    fn bitor(self, other: FeatureSet) -> FeatureSet {
+        FeatureSet {
+            bits: self.bits | other.bits,
+        }
+    }
+}
+
+impl BitOrAssign for FeatureSet {
+    fn bitor_assign(&mut self, other: FeatureSet) {
+        self.bits |= other.bits;
+    }
+}
+
+impl From<Feature> for FeatureSet {
+    fn from(feature: Feature) -> Self {
+        FeatureSet::empty().with(feature)
+    }
+}
+
+impl From<FeatureMode> for FeatureSet {
+    fn from(mode: FeatureMode) -> Self {
+        Feature::ALL[..mode.n_features()].iter().copied().collect()
+    }
+}
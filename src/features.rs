@@ -0,0 +1,41 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Named feature values returned by [`compute_all`](crate::compute_all).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Features {
+    pub names: Vec<String>,
+    pub values: Vec<f64>,
+}
+
+impl Features {
+    /// Version of the serialized form, stored with the values and checked when they are loaded.
+    pub const FORMAT_VERSION: u32 = 1;
+
+    pub fn push(&mut self, name: impl Into<String>, value: f64) {
+        self.names.push(name.into());
+        self.values.push(value);
+    }
+
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| self.values[i])
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.names
+            .iter()
+            .map(|n| n.as_str())
+            .zip(self.values.iter().copied())
+    }
+}
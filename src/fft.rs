@@ -0,0 +1,139 @@
+//! In-place complex FFT of power-of-two length, unnormalised in both directions. With `std` it
+//! is RustFFT's radix-4 algorithm; without, an iterative radix-2 transform.
+
+use alloc::vec::Vec;
+use num_complex::Complex;
+
+/// A transform buffer with the plans for each length it has been used at. Once every length has
+/// been planned and the buffer has grown to fit, transforms run without allocating.
+#[derive(Default)]
+pub(crate) struct FftBuffer {
+    pub(crate) buffer: Vec<Complex<f64>>,
+    #[cfg(feature = "std")]
+    plans: Plans,
+}
+
+impl FftBuffer {
+    /// Plans every power-of-two length up to `max_len` and reserves the buffer for it.
+    pub(crate) fn with_capacity(max_len: usize) -> Self {
+        #[cfg(feature = "std")]
+        let plans = {
+            let mut plans = Plans::default();
+            let mut len = 1;
+            while len <= max_len {
+                plans.plan(len, false);
+                plans.plan(len, true);
+                len <<= 1;
+            }
+            plans
+        };
+        FftBuffer {
+            buffer: Vec::with_capacity(max_len),
+            #[cfg(feature = "std")]
+            plans,
+        }
+    }
+
+    /// Zeroes the buffer at length `len`, which must be a power of two.
+    pub(crate) fn reset(&mut self, len: usize) -> &mut [Complex<f64>] {
+        self.buffer.clear();
+        self.buffer.resize(len, Complex::new(0.0, 0.0));
+        &mut self.buffer
+    }
+
+    /// Transforms the buffer in place.
+    pub(crate) fn process(&mut self, inverse: bool) {
+        #[cfg(feature = "std")]
+        self.plans.process(&mut self.buffer, inverse);
+        #[cfg(not(feature = "std"))]
+        radix2(&mut self.buffer, inverse);
+    }
+}
+
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct Plans {
+    /// Plans indexed by the base-2 logarithm of their length.
+    forward: Vec<Option<rustfft::algorithm::Radix4<f64>>>,
+    inverse: Vec<Option<rustfft::algorithm::Radix4<f64>>>,
+    scratch: Vec<Complex<f64>>,
+}
+
+#[cfg(feature = "std")]
+impl Plans {
+    fn plan(&mut self, len: usize, inverse: bool) {
+        use rustfft::{Fft, FftDirection, algorithm::Radix4};
+
+        let (plans, direction) = if inverse {
+            (&mut self.inverse, FftDirection::Inverse)
+        } else {
+            (&mut self.forward, FftDirection::Forward)
+        };
+        let k = len.trailing_zeros() as usize;
+        if plans.len() <= k {
+            plans.resize_with(k + 1, || None);
+        }
+        let plan = plans[k].get_or_insert_with(|| Radix4::new(len, direction));
+
+        let scratch_len = plan.get_inplace_scratch_len();
+        if self.scratch.len() < scratch_len {
+            self.scratch.resize(scratch_len, Complex::new(0.0, 0.0));
+        }
+    }
+
+    fn process(&mut self, buffer: &mut [Complex<f64>], inverse: bool) {
+        use rustfft::Fft;
+
+        self.plan(buffer.len(), inverse);
+        let k = buffer.len().trailing_zeros() as usize;
+        let plan = if inverse {
+            &self.inverse[k]
+        } else {
+            &self.forward[k]
+        };
+        if let Some(plan) = plan {
+            let scratch_len = plan.get_inplace_scratch_len();
+            plan.process_with_scratch(buffer, &mut self.scratch[..scratch_len]);
+        }
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn radix2(buffer: &mut [Complex<f64>], inverse: bool) {
+    #[cfg(not(test))]
+    use crate::math::Float;
+
+    let n = buffer.len();
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            buffer.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        let angle = sign * 2.0 * core::f64::consts::PI / len as f64;
+        // twiddles are evaluated directly rather than by recurrence to avoid accumulating error
+        for k in 0..half {
+            let w = Complex::new((angle * k as f64).cos(), (angle * k as f64).sin());
+            for start in (0..n).step_by(len) {
+                let u = buffer[start + k];
+                let v = buffer[start + k + half] * w;
+                buffer[start + k] = u + v;
+                buffer[start + k + half] = u - v;
+            }
+        }
+        len <<= 1;
+    }
+}
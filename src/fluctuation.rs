@@ -0,0 +1,466 @@
+//! Fluctuation analysis of the cumulative sum of a series: detrended fluctuation analysis (DFA)
+//! and rescaled range, as used by the `SC_FluctAnal` features.
+//!
+//! The series is integrated, split into non-overlapping windows of size τ for a log-spaced grid
+//! of τ, and the fluctuation F(τ) of each window around a polynomial trend is measured. The
+//! scaling of F(τ) is summarised by a single power law fit and by the best two-segment fit.
+//!
+//! Linear trends are fitted from prefix sums of the integrated series, kept in double-double
+//! precision so that differencing them loses nothing, so each window's fit is O(1) and each scale
+//! costs one pass for the residuals.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+use crate::linalg::lstsq;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+#[cfg(feature = "simd")]
+use crate::simd;
+use crate::statistics::{line_from_sums, linreg, max_, min_, norm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Range of the detrended cumulative sum in each window (`rsrangefit`).
+    RescaledRange,
+    /// Root mean square of the detrended cumulative sum in each window (`dfa`).
+    Dfa,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FluctuationConfig {
+    pub method: Method,
+    /// Order of the polynomial removed from each window (DFA-1, DFA-2, DFA-3).
+    pub order: usize,
+    /// Subsampling step applied before integrating the series.
+    pub lag: usize,
+    /// Smallest window size.
+    pub min_tau: usize,
+    /// Largest window size, half the series length when `None`.
+    pub max_tau: Option<usize>,
+    /// Number of log-spaced window sizes before removing duplicates.
+    pub n_steps: usize,
+    /// Minimum number of scales in each segment of the two-segment fit, at least 2.
+    pub min_points: usize,
+}
+
+impl FluctuationConfig {
+    /// The settings of `SC_FluctAnal_2_*_50_1_logi_prop_r1` for the given method.
+    pub fn new(method: Method) -> Self {
+        FluctuationConfig {
+            method,
+            order: 1,
+            lag: 1,
+            min_tau: 5,
+            max_tau: None,
+            n_steps: 50,
+            min_points: 6,
+        }
+    }
+
+    pub fn with_order(mut self, order: usize) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn with_lag(mut self, lag: usize) -> Self {
+        self.lag = lag;
+        self
+    }
+
+    pub fn with_tau_range(mut self, min_tau: usize, max_tau: usize) -> Self {
+        self.min_tau = min_tau;
+        self.max_tau = Some(max_tau);
+        self
+    }
+
+    pub fn with_steps(mut self, n_steps: usize) -> Self {
+        self.n_steps = n_steps;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fluctuation {
+    /// Distinct window sizes.
+    pub tau: Vec<f64>,
+    /// Fluctuation F(τ) at each window size.
+    pub f: Vec<f64>,
+    /// Scaling exponent of a single power law fitted to all scales (Hurst exponent / DFA α).
+    pub alpha: f64,
+    /// Scaling exponent of the segment before the breakpoint.
+    pub alpha1: f64,
+    /// Scaling exponent of the segment after the breakpoint.
+    pub alpha2: f64,
+    /// Index into `tau` of the scale shared by both segments of the best two-segment fit.
+    pub breakpoint: usize,
+}
+
+impl Fluctuation {
+    /// Window size at the breakpoint.
+    pub fn breakpoint_tau(&self) -> f64 {
+        self.tau[self.breakpoint]
+    }
+
+    /// Position of the breakpoint as a proportion of the number of scales.
+    pub fn breakpoint_prop(&self) -> f64 {
+        (self.breakpoint + 1) as f64 / self.tau.len() as f64
+    }
+}
+
+fn scales_into(len: usize, config: &FluctuationConfig, tau: &mut Vec<f64>) {
+    let lin_low = (config.min_tau as f64).ln();
+    let lin_high = (config.max_tau.unwrap_or(len / 2) as f64).ln();
+
+    let tau_step = (lin_high - lin_low) / (config.n_steps - 1) as f64;
+
+    tau.clear();
+    tau.extend((0..config.n_steps).map(|i| (lin_low + i as f64 * tau_step).exp().round()));
+    tau.dedup();
+}
+
+/// Least-squares polynomial trend of `order` over the positions `1..=tau`, stored as the
+/// design matrix on positions rescaled to [-1, 1] to keep the normal equations well conditioned.
+fn design_matrix(tau: usize, order: usize) -> Vec<f64> {
+    let centre = (tau + 1) as f64 / 2.0;
+    let half_width = ((tau - 1) as f64 / 2.0).max(1.0);
+
+    let mut a = vec![0.0; tau * (order + 1)];
+    for k in 0..tau {
+        let u = ((k + 1) as f64 - centre) / half_width;
+        let mut p = 1.0;
+        for j in 0..order + 1 {
+            a[k * (order + 1) + j] = p;
+            p *= u;
+        }
+    }
+    a
+}
+
+/// A value with the rounding error of its computation carried alongside (double-double
+/// arithmetic), so that the difference of two large prefix sums keeps full precision.
+#[derive(Debug, Clone, Copy, Default)]
+struct Compensated {
+    hi: f64,
+    lo: f64,
+}
+
+impl Compensated {
+    /// `a + b` as a rounded sum and its exact error.
+    fn two_sum(a: f64, b: f64) -> Self {
+        let hi = a + b;
+        let b_virtual = hi - a;
+        let lo = (a - (hi - b_virtual)) + (b - b_virtual);
+        Compensated { hi, lo }
+    }
+
+    /// `a * b` as a rounded product and its exact error, by Dekker's splitting.
+    fn product(a: f64, b: f64) -> Self {
+        let split = |x: f64| {
+            let c = 134217729.0 * x;
+            let hi = c - (c - x);
+            (hi, x - hi)
+        };
+        let (a_hi, a_lo) = split(a);
+        let (b_hi, b_lo) = split(b);
+        let hi = a * b;
+        let lo = ((a_hi * b_hi - hi) + a_hi * b_lo + a_lo * b_hi) + a_lo * b_lo;
+        Compensated { hi, lo }
+    }
+
+    fn add(self, other: Self) -> Self {
+        let s = Compensated::two_sum(self.hi, other.hi);
+        let lo = s.lo + (self.lo + other.lo);
+        let hi = s.hi + lo;
+        Compensated {
+            hi,
+            lo: lo - (hi - s.hi),
+        }
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self.add(Compensated {
+            hi: -other.hi,
+            lo: -other.lo,
+        })
+    }
+
+    fn scale(self, k: f64) -> Self {
+        let p = Compensated::product(self.hi, k);
+        Compensated::two_sum(p.hi, p.lo + self.lo * k)
+    }
+
+    fn value(self) -> f64 {
+        self.hi + self.lo
+    }
+}
+
+/// Reusable buffers for the fluctuation analysis.
+#[derive(Debug, Default)]
+pub(crate) struct FluctuationScratch {
+    tau: Vec<f64>,
+    f: Vec<f64>,
+    y_cs: Vec<f64>,
+    x_reg: Vec<f64>,
+    /// Prefix sums of `y_cs[i]` and `(i + 1) * y_cs[i]`, from which the linear fit of any window
+    /// takes O(1).
+    sum_y: Vec<Compensated>,
+    sum_iy: Vec<Compensated>,
+    buffer: Vec<f64>,
+    logtt: Vec<f64>,
+    logff: Vec<f64>,
+    sserr: Vec<f64>,
+}
+
+impl FluctuationScratch {
+    pub(crate) fn with_capacity(len: usize, config: &FluctuationConfig) -> Self {
+        let max_tau = config.max_tau.unwrap_or(len / 2);
+        let n_steps = config.n_steps;
+        FluctuationScratch {
+            tau: Vec::with_capacity(n_steps),
+            f: Vec::with_capacity(n_steps),
+            y_cs: Vec::with_capacity(len / config.lag.max(1)),
+            x_reg: Vec::with_capacity(max_tau),
+            sum_y: Vec::with_capacity(len / config.lag.max(1) + 1),
+            sum_iy: Vec::with_capacity(len / config.lag.max(1) + 1),
+            buffer: Vec::with_capacity(max_tau.max(n_steps)),
+            logtt: Vec::with_capacity(n_steps),
+            logff: Vec::with_capacity(n_steps),
+            sserr: Vec::with_capacity(n_steps),
+        }
+    }
+}
+
+/// Fills `s.tau` and `s.f` and returns the overall exponent, the exponents of the two segments
+/// and the breakpoint.
+fn fit(
+    a: &[f64],
+    config: &FluctuationConfig,
+    s: &mut FluctuationScratch,
+) -> Result<(f64, f64, f64, usize), Error> {
+    if config.lag == 0 {
+        return Err(Error::InvalidParameter("lag must be at least 1"));
+    }
+    if config.order == 0 || config.order > 3 {
+        return Err(Error::InvalidParameter(
+            "detrending order must be 1, 2 or 3",
+        ));
+    }
+    if config.min_tau <= config.order {
+        return Err(Error::InvalidParameter(
+            "min_tau must exceed the detrending order",
+        ));
+    }
+    if config.n_steps < 2 {
+        return Err(Error::InvalidParameter("n_steps must be at least 2"));
+    }
+    if config.min_points < 2 {
+        return Err(Error::InvalidParameter("min_points must be at least 2"));
+    }
+
+    let max_tau = config.max_tau.unwrap_or(a.len() / 2);
+    if max_tau <= config.min_tau {
+        return Err(Error::TooShort {
+            len: a.len(),
+            min: 2 * (config.min_tau + 1),
+        });
+    }
+
+    let size_cs = a.len() / config.lag;
+    if size_cs < max_tau {
+        return Err(Error::TooShort {
+            len: a.len(),
+            min: max_tau * config.lag,
+        });
+    }
+
+    scales_into(a.len(), config, &mut s.tau);
+    let tau = &s.tau;
+    let n_tau = tau.len();
+
+    if n_tau < 2 * config.min_points {
+        return Err(Error::NotEnoughScales {
+            found: n_tau,
+            required: 2 * config.min_points,
+        });
+    }
+
+    let y_cs = &mut s.y_cs;
+    y_cs.clear();
+    y_cs.resize(size_cs, 0.0);
+
+    y_cs[0] = a[0];
+    for i in 0..size_cs - 1 {
+        y_cs[i + 1] = y_cs[i] + a[(i + 1) * config.lag];
+    }
+
+    let x_reg = &mut s.x_reg;
+    x_reg.clear();
+    x_reg.extend((0..tau[n_tau - 1] as usize).map(|i| (i + 1) as f64));
+
+    if config.order == 1 {
+        s.sum_y.clear();
+        s.sum_iy.clear();
+        let (mut sum_y, mut sum_iy) = (Compensated::default(), Compensated::default());
+        s.sum_y.push(sum_y);
+        s.sum_iy.push(sum_iy);
+        for (i, &y) in y_cs.iter().enumerate() {
+            sum_y = sum_y.add(Compensated { hi: y, lo: 0.0 });
+            sum_iy = sum_iy.add(Compensated::product((i + 1) as f64, y));
+            s.sum_y.push(sum_y);
+            s.sum_iy.push(sum_iy);
+        }
+    }
+
+    let f = &mut s.f;
+    f.clear();
+    f.resize(n_tau, 0.0);
+    for i in 0..n_tau {
+        let t = tau[i] as usize;
+        let n_buffer = (size_cs as f64 / tau[i]) as usize;
+        let buffer = &mut s.buffer;
+        buffer.clear();
+        buffer.resize(t, 0.0);
+
+        let design = if config.order > 1 {
+            design_matrix(t, config.order)
+        } else {
+            Vec::new()
+        };
+
+        // the positions 1..=t are the same in every window
+        let (mut sumx, mut sumx2) = (0.0, 0.0);
+        for x in &x_reg[..t] {
+            sumx += x;
+            sumx2 += x * x;
+        }
+
+        f[i] = 0.0;
+
+        for j in 0..n_buffer {
+            let y = &y_cs[j * t..(j + 1) * t];
+
+            if config.order == 1 {
+                let (start, end) = (j * t, (j + 1) * t);
+                let window_y = s.sum_y[end].sub(s.sum_y[start]);
+                // Σ (k + 1) y[start + k] = Σ (i + 1) y[i] - start Σ y[i] over the window
+                let window_ky = s.sum_iy[end]
+                    .sub(s.sum_iy[start])
+                    .sub(window_y.scale(start as f64));
+                let (m, b) = line_from_sums(t, sumx, sumx2, window_ky.value(), window_y.value());
+
+                for ((r, y), x) in buffer.iter_mut().zip(y).zip(&x_reg[..t]) {
+                    *r = y - (m * x + b);
+                }
+            } else {
+                let coefs = lstsq(t, config.order + 1, &design, y)?;
+
+                for k in 0..t {
+                    let row = &design[k * (config.order + 1)..(k + 1) * (config.order + 1)];
+                    let trend = row.iter().zip(&coefs).map(|(u, c)| u * c).sum::<f64>();
+                    buffer[k] = y[k] - trend;
+                }
+            }
+
+            match config.method {
+                Method::RescaledRange => {
+                    let max = max_(buffer);
+                    let min = min_(buffer);
+                    f[i] += (max - min).powi(2);
+                }
+                #[cfg(feature = "simd")]
+                Method::Dfa => f[i] += simd::dot(buffer, buffer),
+                #[cfg(not(feature = "simd"))]
+                Method::Dfa => {
+                    for k in 0..t {
+                        f[i] += buffer[k].powi(2);
+                    }
+                }
+            }
+        }
+
+        match config.method {
+            Method::RescaledRange => f[i] = (f[i] / n_buffer as f64).sqrt(),
+            Method::Dfa => f[i] = (f[i] / (n_buffer as f64 * tau[i])).sqrt(),
+        }
+    }
+
+    let logtt = &mut s.logtt;
+    let logff = &mut s.logff;
+    logtt.clear();
+    logtt.extend(tau.iter().map(|t| t.ln()));
+    logff.clear();
+    logff.extend(f.iter().map(|f| f.ln()));
+
+    let ntt = n_tau;
+
+    let (alpha, _) = linreg(ntt, logtt, logff);
+
+    let min_points = config.min_points;
+
+    let nsserr = ntt - 2 * min_points + 1;
+
+    let sserr = &mut s.sserr;
+    sserr.clear();
+    sserr.resize(nsserr, 0.0);
+    let buffer = &mut s.buffer;
+    buffer.clear();
+    buffer.resize(ntt - min_points + 1, 0.0);
+
+    for i in min_points..ntt - min_points + 1 {
+        let (m1, b1) = linreg(i, logtt, logff);
+        let (m2, b2) = linreg(ntt - i + 1, &logtt[i - 1..], &logff[i - 1..]);
+
+        for j in 0..i {
+            buffer[j] = logtt[j] * m1 + b1 - logff[j];
+        }
+
+        sserr[i - min_points] += norm(&buffer[..i]);
+
+        for j in 0..ntt - i + 1 {
+            buffer[j] = logtt[j + i - 1] * m2 + b2 - logff[j + i - 1];
+        }
+
+        sserr[i - min_points] += norm(&buffer[..ntt - i + 1]);
+    }
+
+    let mut first_min_ind = 0;
+    let minimum = min_(sserr);
+    for i in 0..nsserr {
+        if sserr[i] == minimum {
+            first_min_ind = i + min_points - 1;
+            break;
+        }
+    }
+
+    let split = first_min_ind + 1;
+    let (alpha1, _) = linreg(split, logtt, logff);
+    let (alpha2, _) = linreg(ntt - split + 1, &logtt[split - 1..], &logff[split - 1..]);
+
+    Ok((alpha, alpha1, alpha2, first_min_ind))
+}
+
+pub fn fluctuation(a: &[f64], config: &FluctuationConfig) -> Result<Fluctuation, Error> {
+    let mut s = FluctuationScratch::default();
+    let (alpha, alpha1, alpha2, breakpoint) = fit(a, config, &mut s)?;
+
+    Ok(Fluctuation {
+        tau: s.tau,
+        f: s.f,
+        alpha,
+        alpha1,
+        alpha2,
+        breakpoint,
+    })
+}
+
+/// [`Fluctuation::breakpoint_prop`] of the analysis, computed in reusable buffers.
+pub(crate) fn breakpoint_prop_with(
+    a: &[f64],
+    config: &FluctuationConfig,
+    s: &mut FluctuationScratch,
+) -> Result<f64, Error> {
+    let (_, _, _, breakpoint) = fit(a, config, s)?;
+    Ok((breakpoint + 1) as f64 / s.tau.len() as f64)
+}
@@ -0,0 +1,318 @@
+//! One- and two-dimensional histograms, as used by the histogram-mode, automutual-information and
+//! embedding-distance features.
+//!
+//! Equal-width histograms assign each value to a bin arithmetically; histograms with explicit
+//! edges assign by binary search, so binning costs O(n log bins). Bin `k` covers
+//! `[edges[k], edges[k + 1])`, except the last bin, which also includes its right edge. Values
+//! outside the edges are not counted.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::quantile::{Method, quantiles};
+use crate::statistics::{max_, min_, std_dev};
+
+/// Rules for choosing the number of equal-width bins from the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinRule {
+    /// Width `3.5 σ n^(-1/3)`.
+    Scott,
+    /// Width `2 IQR n^(-1/3)`.
+    FreedmanDiaconis,
+    /// `ceil(log2 n) + 1` bins.
+    Sturges,
+}
+
+impl BinRule {
+    /// Number of bins spanning the range of `a`, 0 if the series has (almost) no spread.
+    pub fn n_bins(&self, a: &[f64]) -> usize {
+        if a.len() < 2 {
+            return 0;
+        }
+        let range = max_(a) - min_(a);
+        let n = a.len() as f64;
+
+        match self {
+            BinRule::Scott => {
+                let sd = std_dev(a);
+                if sd < 0.001 {
+                    return 0;
+                }
+                (range / (3.5 * sd * n.powf(-1.0 / 3.0))).ceil() as usize
+            }
+            BinRule::FreedmanDiaconis => {
+                let q = quantiles(a, &[0.25, 0.75], Method::Matlab);
+                let iqr = q[1] - q[0];
+                if iqr <= 0.0 {
+                    return 0;
+                }
+                (range / (2.0 * iqr * n.powf(-1.0 / 3.0))).ceil() as usize
+            }
+            BinRule::Sturges => {
+                if range == 0.0 {
+                    return 0;
+                }
+                n.log2().ceil() as usize + 1
+            }
+        }
+    }
+}
+
+pub(crate) fn check_edges(edges: &[f64]) -> Result<(), Error> {
+    if edges.len() < 2 {
+        return Err(Error::InvalidParameter("at least 2 bin edges are required"));
+    }
+    if edges.iter().any(|e| !e.is_finite()) || edges.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(Error::InvalidParameter(
+            "bin edges must be finite and strictly increasing",
+        ));
+    }
+    Ok(())
+}
+
+/// Bin of `x` among the given edges.
+pub fn bin_index(edges: &[f64], x: f64) -> Option<usize> {
+    let n_bins = edges.len() - 1;
+    if x == edges[n_bins] {
+        return Some(n_bins - 1);
+    }
+    // first edge above x
+    let upper = edges.partition_point(|&e| e <= x);
+    if upper == 0 || upper > n_bins {
+        return None;
+    }
+    Some(upper - 1)
+}
+
+/// Counts of `counts.len()` equal-width bins between the minimum and maximum of a non-empty
+/// series, added to `counts`. Returns the minimum and the bin width.
+pub(crate) fn equal_width_counts(a: &[f64], counts: &mut [usize]) -> (f64, f64) {
+    let n_bins = counts.len();
+    let max_val = max_(a);
+    let min_val = min_(a);
+    let bin_step = (max_val - min_val) / n_bins as f64;
+
+    for i in 0..a.len() {
+        let mut bin_ind = ((a[i] - min_val) / bin_step) as usize;
+        bin_ind = bin_ind.min(n_bins - 1);
+
+        counts[bin_ind] += 1;
+    }
+
+    (min_val, bin_step)
+}
+
+/// Joint counts of the pairs `(x[i], y[i])` within the edges, added to the row-major `counts`.
+pub(crate) fn joint_counts(
+    x: &[f64],
+    y: &[f64],
+    x_edges: &[f64],
+    y_edges: &[f64],
+    counts: &mut [usize],
+) {
+    let ny = y_edges.len() - 1;
+    for (&u, &v) in x.iter().zip(y) {
+        if let (Some(i), Some(j)) = (bin_index(x_edges, u), bin_index(y_edges, v)) {
+            counts[i * ny + j] += 1;
+        }
+    }
+}
+
+/// Mutual information in nats of the row-major `nx × ny` joint counts, with the marginal
+/// probabilities left in `marginals` (`nx + ny` values).
+pub(crate) fn mutual_information_of(
+    counts: &[usize],
+    nx: usize,
+    ny: usize,
+    marginals: &mut [f64],
+) -> f64 {
+    let total = counts.iter().sum::<usize>() as f64;
+    let p = |i: usize, j: usize| counts[i * ny + j] as f64 / total;
+
+    let (px, py) = marginals.split_at_mut(nx);
+    px.fill(0.0);
+    py.fill(0.0);
+    for i in 0..nx {
+        for j in 0..ny {
+            px[i] += p(i, j);
+        }
+    }
+    for j in 0..ny {
+        for i in 0..nx {
+            py[j] += p(i, j);
+        }
+    }
+
+    let mut mi = 0.0;
+    for j in 0..ny {
+        for i in 0..nx {
+            let p_ij = p(i, j);
+            if p_ij > 0.0 {
+                mi += p_ij * (p_ij / (py[j] * px[i])).ln();
+            }
+        }
+    }
+    mi
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub edges: Vec<f64>,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    pub fn with_edges(a: &[f64], edges: &[f64]) -> Result<Histogram, Error> {
+        check_edges(edges)?;
+
+        let mut counts = vec![0; edges.len() - 1];
+        for &x in a {
+            if let Some(bin) = bin_index(edges, x) {
+                counts[bin] += 1;
+            }
+        }
+
+        Ok(Histogram {
+            edges: edges.to_vec(),
+            counts,
+        })
+    }
+
+    /// `n_bins` bins of equal width between the minimum and maximum. A constant series puts
+    /// every value in the first bin.
+    pub fn equal_width(a: &[f64], n_bins: usize) -> Result<Histogram, Error> {
+        if n_bins == 0 {
+            return Err(Error::InvalidParameter("n_bins must be at least 1"));
+        }
+        if a.is_empty() {
+            return Err(Error::TooShort { len: 0, min: 1 });
+        }
+
+        let mut counts = vec![0; n_bins];
+        let (min_val, bin_step) = equal_width_counts(a, &mut counts);
+
+        let mut edges = vec![0.0; n_bins + 1];
+        for i in 0..n_bins + 1 {
+            edges[i] = min_val + i as f64 * bin_step;
+        }
+
+        Ok(Histogram { edges, counts })
+    }
+
+    /// Equal-width bins, as many as the rule gives.
+    pub fn with_rule(a: &[f64], rule: BinRule) -> Result<Histogram, Error> {
+        match rule.n_bins(a) {
+            0 => Err(Error::ConstantSeries),
+            n_bins => Histogram::equal_width(a, n_bins),
+        }
+    }
+
+    pub fn n_bins(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Bin of `x`, `None` if it lies outside the edges.
+    pub fn bin(&self, x: f64) -> Option<usize> {
+        bin_index(&self.edges, x)
+    }
+
+    pub fn centers(&self) -> Vec<f64> {
+        self.edges.windows(2).map(|w| (w[0] + w[1]) / 2.0).collect()
+    }
+
+    pub fn widths(&self) -> Vec<f64> {
+        self.edges.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Number of values counted.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Proportion of the counted values in each bin.
+    pub fn probabilities(&self) -> Vec<f64> {
+        let total = self.total() as f64;
+        self.counts.iter().map(|&c| c as f64 / total).collect()
+    }
+
+    /// Probability density in each bin, integrating to 1 over the edges.
+    pub fn density(&self) -> Vec<f64> {
+        self.probabilities()
+            .iter()
+            .zip(self.widths())
+            .map(|(p, w)| p / w)
+            .collect()
+    }
+}
+
+/// Joint histogram of pairs `(x[i], y[i])`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram2d {
+    pub x_edges: Vec<f64>,
+    pub y_edges: Vec<f64>,
+    /// `counts[i][j]` is the number of pairs in x bin `i` and y bin `j`.
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl Histogram2d {
+    /// Pairs with either value outside its edges are not counted.
+    pub fn with_edges(
+        x: &[f64],
+        y: &[f64],
+        x_edges: &[f64],
+        y_edges: &[f64],
+    ) -> Result<Histogram2d, Error> {
+        check_edges(x_edges)?;
+        check_edges(y_edges)?;
+        if x.len() != y.len() {
+            return Err(Error::InvalidParameter("x and y must have the same length"));
+        }
+
+        let ny = y_edges.len() - 1;
+        let mut flat = vec![0; (x_edges.len() - 1) * ny];
+        joint_counts(x, y, x_edges, y_edges, &mut flat);
+        let counts = flat.chunks(ny).map(<[usize]>::to_vec).collect();
+
+        Ok(Histogram2d {
+            x_edges: x_edges.to_vec(),
+            y_edges: y_edges.to_vec(),
+            counts,
+        })
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Joint probabilities, `p[i][j]` for x bin `i` and y bin `j`.
+    pub fn probabilities(&self) -> Vec<Vec<f64>> {
+        let total = self.total() as f64;
+        self.counts
+            .iter()
+            .map(|row| row.iter().map(|&c| c as f64 / total).collect())
+            .collect()
+    }
+
+    /// Joint probability density, integrating to 1 over the edges.
+    pub fn density(&self) -> Vec<Vec<f64>> {
+        let mut p = self.probabilities();
+        for i in 0..p.len() {
+            let w_x = self.x_edges[i + 1] - self.x_edges[i];
+            for j in 0..p[i].len() {
+                p[i][j] /= w_x * (self.y_edges[j + 1] - self.y_edges[j]);
+            }
+        }
+        p
+    }
+
+    /// Mutual information of the binned values in nats.
+    pub fn mutual_information(&self) -> f64 {
+        let nx = self.x_edges.len() - 1;
+        let ny = self.y_edges.len() - 1;
+        let counts = self.counts.concat();
+        mutual_information_of(&counts, nx, ny, &mut vec![0.0; nx + ny])
+    }
+}
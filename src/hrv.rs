@@ -0,0 +1,348 @@
+//! Classic heart-rate-variability measures on RR-interval series.
+//!
+//! All functions take the RR intervals together with the [`TimeUnit`] they are expressed in and
+//! report times in milliseconds.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::TimeUnit;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::statistics::{diff, mean, std_dev, welch};
+
+/// Width of the histogram bins used by the geometric measures (1/128 s), as recommended by the
+/// Task Force of the ESC/NASPE (1996).
+pub const HISTOGRAM_BIN_WIDTH_MS: f64 = 1000.0 / 128.0;
+
+/// Most bins of the histogram used by the geometric measures, spanning 32 s. Series with a wider
+/// range of intervals are not RR intervals in the given unit, and give NaN.
+pub const MAX_HISTOGRAM_BINS: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeDomain {
+    pub mean_nn: f64,
+    pub sdnn: f64,
+    pub rmssd: f64,
+    pub sdsd: f64,
+    pub pnn20: f64,
+    pub pnn50: f64,
+    pub triangular_index: f64,
+    pub tinn: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Poincare {
+    pub sd1: f64,
+    pub sd2: f64,
+}
+
+impl Poincare {
+    pub fn ratio(&self) -> f64 {
+        self.sd1 / self.sd2
+    }
+}
+
+/// Settings for the spectral measures. Bands are given in Hz as `(low, high)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyConfig {
+    /// Rate in Hz of the uniform grid the RR intervals are interpolated on.
+    pub resample_rate: f64,
+    /// Length of the Welch segments in resampled points, at least 2; the band powers are NaN
+    /// otherwise.
+    pub segment_len: usize,
+    pub vlf: (f64, f64),
+    pub lf: (f64, f64),
+    pub hf: (f64, f64),
+}
+
+impl Default for FrequencyConfig {
+    fn default() -> Self {
+        FrequencyConfig {
+            resample_rate: 4.0,
+            segment_len: 256,
+            vlf: (0.0033, 0.04),
+            lf: (0.04, 0.15),
+            hf: (0.15, 0.4),
+        }
+    }
+}
+
+/// Band powers in ms².
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrequencyDomain {
+    pub vlf: f64,
+    pub lf: f64,
+    pub hf: f64,
+    pub total: f64,
+}
+
+impl FrequencyDomain {
+    pub fn lf_hf(&self) -> f64 {
+        self.lf / self.hf
+    }
+
+    pub fn lf_nu(&self) -> f64 {
+        self.lf / (self.lf + self.hf)
+    }
+
+    pub fn hf_nu(&self) -> f64 {
+        self.hf / (self.lf + self.hf)
+    }
+}
+
+fn to_millis(rr: &[f64], unit: TimeUnit) -> Vec<f64> {
+    rr.iter().map(|&x| unit.to_millis(x)).collect()
+}
+
+/// Proportion of successive differences larger than `x_ms` milliseconds (pNNx).
+pub fn pnn(rr: &[f64], x_ms: f64, unit: TimeUnit) -> f64 {
+    if rr.len() < 2 {
+        return f64::NAN;
+    }
+
+    let mut count = 0.0;
+    for i in 0..rr.len() - 1 {
+        if unit.to_millis((rr[i + 1] - rr[i]).abs()) > x_ms {
+            count += 1.0;
+        }
+    }
+
+    return count / (rr.len() - 1) as f64;
+}
+
+/// Root mean square of successive differences.
+pub fn rmssd(rr: &[f64], unit: TimeUnit) -> f64 {
+    if rr.len() < 2 {
+        return f64::NAN;
+    }
+    let rr = to_millis(rr, unit);
+    let d_y = diff(&rr);
+    let sum_sq = d_y[..rr.len() - 1].iter().map(|x| x * x).sum::<f64>();
+    return (sum_sq / (rr.len() - 1) as f64).sqrt();
+}
+
+/// Standard deviation of the RR intervals.
+pub fn sdnn(rr: &[f64], unit: TimeUnit) -> f64 {
+    if rr.len() < 2 {
+        return f64::NAN;
+    }
+    std_dev(&to_millis(rr, unit))
+}
+
+/// Standard deviation of successive differences.
+pub fn sdsd(rr: &[f64], unit: TimeUnit) -> f64 {
+    if rr.len() < 3 {
+        return f64::NAN;
+    }
+    let rr = to_millis(rr, unit);
+    let d_y = diff(&rr);
+    std_dev(&d_y[..rr.len() - 1])
+}
+
+/// Histogram of the intervals, `None` if any is not finite or they span more than
+/// [`MAX_HISTOGRAM_BINS`] bins.
+fn rr_histogram(rr_ms: &[f64]) -> Option<Vec<usize>> {
+    if rr_ms.iter().any(|x| !x.is_finite()) {
+        return None;
+    }
+    let min_val = rr_ms.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max_val = rr_ms.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+    let span = ((max_val - min_val) / HISTOGRAM_BIN_WIDTH_MS).floor();
+    if span >= MAX_HISTOGRAM_BINS as f64 {
+        return None;
+    }
+    let n_bins = span as usize + 1;
+    let mut counts = vec![0; n_bins];
+
+    for &x in rr_ms {
+        let bin = ((x - min_val) / HISTOGRAM_BIN_WIDTH_MS) as usize;
+        counts[bin.min(n_bins - 1)] += 1;
+    }
+
+    Some(counts)
+}
+
+/// HRV triangular index: number of intervals divided by the height of their histogram.
+pub fn triangular_index(rr: &[f64], unit: TimeUnit) -> f64 {
+    if rr.is_empty() {
+        return f64::NAN;
+    }
+    let Some(counts) = rr_histogram(&to_millis(rr, unit)) else {
+        return f64::NAN;
+    };
+    let max_count = *counts.iter().max().unwrap();
+    return rr.len() as f64 / max_count as f64;
+}
+
+/// Triangular interpolation of the NN histogram: base width `M - N` of the triangle that best
+/// fits the histogram in the least-squares sense, with its apex at the histogram mode.
+pub fn tinn(rr: &[f64], unit: TimeUnit) -> f64 {
+    if rr.len() < 2 {
+        return f64::NAN;
+    }
+    let Some(counts) = rr_histogram(&to_millis(rr, unit)) else {
+        return f64::NAN;
+    };
+    let n_bins = counts.len();
+
+    let mut x = 0;
+    for i in 1..n_bins {
+        if counts[i] > counts[x] {
+            x = i;
+        }
+    }
+
+    // N and M are bin boundaries expressed in bins; bin i spans [i, i + 1) with centre i + 0.5.
+    // The squared error splits into a term in N, over the bins up to the mode, and one in M,
+    // over the bins after it, so the two are minimised separately. Scaled by the square of
+    // twice the distance from the boundary to the apex, each term is an integer, so ties are
+    // exact and go to the first boundary.
+    let count = |i: usize| counts[i] as i128;
+    let y = count(x);
+    let mut below = vec![0; n_bins + 1];
+    for i in 0..n_bins {
+        below[i + 1] = below[i] + count(i).pow(2);
+    }
+
+    let left = (0..=x).map(|n| {
+        let d = (2 * (x - n) + 1) as i128;
+        let err = (n..=x)
+            .map(|i| (count(i) * d - y * (2 * (i - n) + 1) as i128).pow(2))
+            .sum::<i128>();
+        (n, below[n] * d * d + err, d * d)
+    });
+    let right = (x + 1..=n_bins).map(|m| {
+        let d = (2 * (m - x) - 1) as i128;
+        let err = (x + 1..m)
+            .map(|i| (count(i) * d - y * (2 * (m - i) - 1) as i128).pow(2))
+            .sum::<i128>();
+        (m, (below[n_bins] - below[m]) * d * d + err, d * d)
+    });
+    let by_error = |a: &(usize, i128, i128), b: &(usize, i128, i128)| (a.1 * b.2).cmp(&(b.1 * a.2));
+    let (n, _, _) = left.min_by(by_error).unwrap();
+    let (m, _, _) = right.min_by(by_error).unwrap();
+
+    return (m - n) as f64 * HISTOGRAM_BIN_WIDTH_MS;
+}
+
+pub fn time_domain(rr: &[f64], unit: TimeUnit) -> TimeDomain {
+    TimeDomain {
+        mean_nn: mean(&to_millis(rr, unit)),
+        sdnn: sdnn(rr, unit),
+        rmssd: rmssd(rr, unit),
+        sdsd: sdsd(rr, unit),
+        pnn20: pnn(rr, 20.0, unit),
+        pnn50: pnn(rr, 50.0, unit),
+        triangular_index: triangular_index(rr, unit),
+        tinn: tinn(rr, unit),
+    }
+}
+
+/// SD1 and SD2 of the Poincaré plot of successive intervals.
+pub fn poincare(rr: &[f64], unit: TimeUnit) -> Poincare {
+    if rr.len() < 3 {
+        return Poincare {
+            sd1: f64::NAN,
+            sd2: f64::NAN,
+        };
+    }
+    let rr = to_millis(rr, unit);
+
+    let mut minor = vec![0.0; rr.len() - 1];
+    let mut major = vec![0.0; rr.len() - 1];
+    for i in 0..rr.len() - 1 {
+        minor[i] = (rr[i + 1] - rr[i]) / 2.0f64.sqrt();
+        major[i] = (rr[i + 1] + rr[i]) / 2.0f64.sqrt();
+    }
+
+    Poincare {
+        sd1: std_dev(&minor),
+        sd2: std_dev(&major),
+    }
+}
+
+/// Linearly interpolates the RR tachogram, sampled at the beat times, on a uniform grid of
+/// `rate` Hz. Returns the resampled intervals in ms.
+pub fn resample(rr: &[f64], unit: TimeUnit, rate: f64) -> Vec<f64> {
+    let rr = to_millis(rr, unit);
+    if rr.len() < 2 {
+        return rr;
+    }
+
+    // beat times in seconds, starting at the end of the first interval
+    let times = rr
+        .iter()
+        .scan(0.0, |t, x| {
+            *t += x / 1000.0;
+            Some(*t)
+        })
+        .collect::<Vec<f64>>();
+
+    let t0 = times[0];
+    let n_out = ((times[times.len() - 1] - t0) * rate).floor() as usize + 1;
+    let mut out = vec![0.0; n_out];
+
+    let mut k = 0;
+    for i in 0..n_out {
+        let t = t0 + i as f64 / rate;
+        while k + 2 < times.len() && times[k + 1] < t {
+            k += 1;
+        }
+        let w = (t - times[k]) / (times[k + 1] - times[k]);
+        out[i] = rr[k] + w * (rr[k + 1] - rr[k]);
+    }
+
+    out
+}
+
+/// Band powers from the Welch PSD (Hann window, half-overlapping segments) of the resampled
+/// tachogram.
+pub fn frequency_domain(rr: &[f64], unit: TimeUnit, config: &FrequencyConfig) -> FrequencyDomain {
+    let mut y = resample(rr, unit, config.resample_rate);
+    let nan = FrequencyDomain {
+        vlf: f64::NAN,
+        lf: f64::NAN,
+        hf: f64::NAN,
+        total: f64::NAN,
+    };
+    if y.len() < 4 || config.segment_len < 2 {
+        return nan;
+    }
+
+    // welch removes the mean after windowing, so remove it here to avoid leaking it into VLF
+    let m = mean(&y);
+    y.iter_mut().for_each(|x| *x -= m);
+
+    let segment_len = config.segment_len.min(y.len());
+    let window = (0..segment_len)
+        .map(|i| {
+            0.5 - 0.5 * (2.0 * core::f64::consts::PI * i as f64 / (segment_len - 1) as f64).cos()
+        })
+        .collect::<Vec<f64>>();
+
+    let (pxx, f) = welch(&y, config.resample_rate, &window);
+    let df = f[1] - f[0];
+
+    let band = |(low, high): (f64, f64)| {
+        let mut power = 0.0;
+        for i in 0..f.len() {
+            if f[i] >= low && f[i] < high {
+                power += pxx[i] * df;
+            }
+        }
+        power
+    };
+
+    let vlf = band(config.vlf);
+    let lf = band(config.lf);
+    let hf = band(config.hf);
+
+    FrequencyDomain {
+        vlf,
+        lf,
+        hf,
+        total: vlf + lf + hf,
+    }
+}
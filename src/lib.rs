@@ -1,32 +1,128 @@
+#![no_std]
+#![allow(clippy::needless_range_loop, clippy::needless_return)]
+
+extern crate alloc;
+// run-time detection of the instruction sets the SIMD kernels dispatch on
+#[cfg(feature = "simd")]
+extern crate std;
+
+use alloc::format;
+use alloc::vec::Vec;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+pub mod ami;
+pub mod autocorrelation;
 mod catch22;
+pub mod classify;
+pub mod entropy;
+mod error;
+mod feature_set;
+mod features;
+mod fft;
+pub mod fluctuation;
+pub mod histogram;
+pub mod hrv;
+mod linalg;
+// the test harness links std, whose inherent float methods then stand in for these
+#[cfg(not(any(feature = "std", test)))]
+mod math;
+mod options;
+pub mod outliers;
+pub mod periodicity;
+#[cfg(feature = "pipeline")]
+pub mod pipeline;
+pub mod quantile;
+mod registry;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "simd")]
+mod simd;
+pub mod spline;
 mod statistics;
+pub mod symbolic;
+pub mod transition;
+mod workspace;
 
-pub const N_CATCH22: usize = 25;
+pub use error::Error;
+pub use feature_set::FeatureSet;
+pub use features::Features;
+pub use options::{EntropyOptions, FeatureMode, LagUnit, Options, TimeUnit};
+pub use registry::{Feature, FeatureInfo, Parameter};
+pub use workspace::Catch22Workspace;
+
+/// Number of catch22 features.
+pub const N_CATCH22: usize = 22;
+/// Number of catch24 features: catch22 with the mean and standard deviation.
+pub const N_CATCH24: usize = 24;
+/// Number of features in [`FEATURE_NAMES`]: catch24 with the slope.
+pub const N_FEATURES: usize = 25;
+
+/// Names of the features, indexed as in [`compute`]: the catch22 features, `DN_Mean` and
+/// `DN_Spread_Std` as in pycatch22's catch24, and `SY_Slope`.
+pub const FEATURE_NAMES: [&str; N_FEATURES] = [
+    "DN_OutlierInclude_n_001_mdrmd",
+    "DN_OutlierInclude_p_001_mdrmd",
+    "DN_HistogramMode_5",
+    "DN_HistogramMode_10",
+    "CO_Embed2_Dist_tau_d_expfit_meandiff",
+    "CO_f1ecac",
+    "CO_FirstMin_ac",
+    "CO_HistogramAMI_even_2_5",
+    "CO_trev_1_num",
+    "FC_LocalSimple_mean1_tauresrat",
+    "FC_LocalSimple_mean3_stderr",
+    "IN_AutoMutualInfoStats_40_gaussian_fmmi",
+    "MD_hrv_classic_pnn40",
+    "SB_BinaryStats_diff_longstretch0",
+    "SB_BinaryStats_mean_longstretch1",
+    "SB_MotifThree_quantile_hh",
+    "SC_FluctAnal_2_rsrangefit_50_1_logi_prop_r1",
+    "SC_FluctAnal_2_dfa_50_1_2_logi_prop_r1",
+    "SP_Summaries_welch_rect_area_5_1",
+    "SP_Summaries_welch_rect_centroid",
+    "SB_TransitionMatrix_3ac_sumdiagcov",
+    "PD_PeriodicityWang_th0_01",
+    "DN_Mean",
+    "DN_Spread_Std",
+    "SY_Slope",
+];
 
 pub fn compute(x: &[f64], n: usize) -> f64 {
+    compute_with(x, n, &Options::default())
+}
+
+pub fn compute_with(x: &[f64], n: usize, options: &Options) -> f64 {
+    compute_in(&mut Catch22Workspace::new(), x, n, options)
+}
+
+fn compute_in(ws: &mut Catch22Workspace, x: &[f64], n: usize, options: &Options) -> f64 {
     match n {
-        0 => catch22::dn_outlier_include_np_001_mdrmd(x, false),
-        1 => catch22::dn_outlier_include_np_001_mdrmd(x, true),
-        2 => catch22::dn_histogram_mode_n(x, 5),
-        3 => catch22::dn_histogram_mode_n(x, 10),
-        4 => catch22::co_embed2_dist_tau_d_expfit_meandiff(x),
-        5 => catch22::co_f1ecac(x),
-        6 => catch22::co_first_min_ac(x),
-        7 => catch22::co_histogram_ami_even_tau_bins(x, 2, 5),
+        0 => catch22::dn_outlier_include_np_001_mdrmd(ws, x, false),
+        1 => catch22::dn_outlier_include_np_001_mdrmd(ws, x, true),
+        2 => catch22::dn_histogram_mode_n(ws, x, 5),
+        3 => catch22::dn_histogram_mode_n(ws, x, 10),
+        4 => catch22::co_embed2_dist_tau_d_expfit_meandiff(ws, x),
+        5 => options.lag(catch22::co_f1ecac(ws, x)),
+        6 => options.lag(catch22::co_first_min_ac(ws, x)),
+        7 => catch22::co_histogram_ami_even_tau_bins(ws, x, 2, 5),
         8 => catch22::co_trev_1_num(x),
-        9 => catch22::fc_local_simple_mean_tauresrat(x, 1),
-        10 => catch22::fc_local_simple_mean_stderr(x, 3),
-        11 => catch22::in_auto_mutual_info_stats_tau_gaussian_fmmi(x, 40.0),
-        12 => catch22::md_hrv_classic_pnn(x, 40),
+        9 => catch22::fc_local_simple_mean_tauresrat(ws, x, 1),
+        10 => catch22::fc_local_simple_mean_stderr(ws, x, 3),
+        11 => catch22::in_auto_mutual_info_stats_tau_gaussian_fmmi(ws, x, 40.0),
+        12 => catch22::md_hrv_classic_pnn(x, 40.0, options.value_unit),
         13 => catch22::sb_binary_stats_diff_longstretch0(x),
         14 => catch22::sb_binary_stats_mean_longstretch1(x),
-        15 => catch22::sb_motif_three_quantile_hh(x),
-        16 => catch22::sc_fluct_anal_2_50_1_logi_prop_r1(x, 1, "rsrangefit"),
-        17 => catch22::sc_fluct_anal_2_50_1_logi_prop_r1(x, 2, "dfa"),
-        18 => catch22::sp_summaries_welch_rect(x, "area_5_1"),
-        19 => catch22::sp_summaries_welch_rect(x, "centroid"),
-        20 => catch22::sb_transition_matrix_3ac_sumdiagcov(x),
-        21 => catch22::pd_periodicity_wang_th0_01(x),
+        15 => catch22::sb_motif_three_quantile_hh(ws, x),
+        16 => {
+            catch22::sc_fluct_anal_2_50_1_logi_prop_r1(ws, x, 1, fluctuation::Method::RescaledRange)
+        }
+        17 => catch22::sc_fluct_anal_2_50_1_logi_prop_r1(ws, x, 2, fluctuation::Method::Dfa),
+        18 => catch22::sp_summaries_welch_rect(ws, x, "area_5_1", options.sampling_rate),
+        19 => catch22::sp_summaries_welch_rect(ws, x, "centroid", options.sampling_rate),
+        20 => catch22::sb_transition_matrix_3ac_sumdiagcov(ws, x),
+        21 => options.lag(catch22::pd_periodicity_wang_th0_01(ws, x)),
         22 => statistics::mean(x),
         23 => statistics::std_dev(x),
         24 => statistics::slope(x),
@@ -34,6 +130,94 @@ pub fn compute(x: &[f64], n: usize) -> f64 {
     }
 }
 
+/// Computes the catch24 features ([`FeatureMode::Catch24`]) with the default [`Options`], using
+/// the buffers of `workspace`.
+///
+/// With a workspace from [`Catch22Workspace::with_capacity`] and a series no longer than its
+/// capacity, this performs no heap allocation, so it can run in real-time loops. Results are
+/// identical to [`compute`].
+pub fn compute_all_into(workspace: &mut Catch22Workspace, x: &[f64], out: &mut [f64; N_CATCH24]) {
+    let options = Options::default();
+    workspace.start();
+    for (i, value) in out.iter_mut().enumerate() {
+        *value = compute_in(workspace, x, i, &options);
+    }
+}
+
+/// Computes the features of `set` with the default [`Options`].
+pub fn compute_set(x: &[f64], set: &FeatureSet) -> Features {
+    compute_set_with(x, set, &Options::default())
+}
+
+/// Computes the features of `set`, in the order of [`FEATURE_NAMES`]. Intermediates such as the
+/// autocorrelation and the sorted series are computed once and shared between the features that
+/// use them; the values are identical to [`compute_with`]. Features whose
+/// [`FeatureInfo::min_length`] exceeds the length of `x` are NaN.
+pub fn compute_set_with(x: &[f64], set: &FeatureSet, options: &Options) -> Features {
+    compute_set_in(&mut Catch22Workspace::new(), x, set, options)
+}
+
+pub(crate) fn compute_set_in(
+    ws: &mut Catch22Workspace,
+    x: &[f64],
+    set: &FeatureSet,
+    options: &Options,
+) -> Features {
+    let mut features = Features::default();
+    ws.start();
+
+    for feature in set.iter() {
+        let value = if x.len() < feature.info().min_length {
+            f64::NAN
+        } else {
+            compute_in(ws, x, feature.index(), options)
+        };
+        features.push(feature.name(), value);
+    }
+    features
+}
+
+/// Computes the features of `options.mode`, followed by the entropy features when
+/// `options.entropy` is set.
+pub fn compute_all(x: &[f64], options: &Options) -> Features {
+    let mut features = Features::default();
+    let mut workspace = Catch22Workspace::new();
+
+    for (i, name) in options.mode.names().iter().enumerate() {
+        features.push(*name, compute_in(&mut workspace, x, i, options));
+    }
+
+    if let Some(en) = options.entropy {
+        push_entropy(&mut features, x, &en);
+    }
+
+    features
+}
+
+/// Appends the entropy features of `x` to `features`.
+pub(crate) fn push_entropy(features: &mut Features, x: &[f64], en: &EntropyOptions) {
+    let r = en.tolerance * statistics::std_dev(x);
+
+    features.push(
+        format!("EN_SampEn_m{}", en.m),
+        entropy::sample_entropy(x, en.m, r),
+    );
+    features.push(
+        format!("EN_ApEn_m{}", en.m),
+        entropy::approximate_entropy(x, en.m, r),
+    );
+    features.push(
+        format!("EN_PermEn_d{}_tau{}", en.order, en.delay),
+        entropy::permutation_entropy(x, en.order, en.delay),
+    );
+    for (i, value) in entropy::multiscale_entropy(x, en.m, r, en.max_scale)
+        .into_iter()
+        .enumerate()
+    {
+        features.push(format!("EN_MSE_m{}_scale{}", en.m, i + 1), value);
+    }
+}
+
 pub fn zscore(x: &[f64]) -> Vec<f64> {
     let mean = x.iter().sum::<f64>() / x.len() as f64;
     let std = (x.iter().map(|val| (val - mean).powi(2)).sum::<f64>() / x.len() as f64).sqrt();
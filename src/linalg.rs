@@ -0,0 +1,176 @@
+//! Small solvers for the least-squares fits behind the spline and fluctuation analyses: dense
+//! matrices are row-major slices, banded ones are added a row at a time.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+/// Least-squares solution of `a x = b` for `a` of `rows × cols`, by Householder QR with column
+/// pivoting. Fails if `a` is rank deficient to working precision.
+pub(crate) fn lstsq(rows: usize, cols: usize, a: &[f64], b: &[f64]) -> Result<Vec<f64>, Error> {
+    if rows < cols {
+        return Err(Error::SingularSystem);
+    }
+    let mut r = a.to_vec();
+    let mut qtb = b.to_vec();
+    let mut perm = (0..cols).collect::<Vec<usize>>();
+    let mut diag = vec![0.0; cols];
+
+    for k in 0..cols {
+        // pivot on the column with the largest remaining norm
+        let mut p = k;
+        let mut p_norm = -1.0;
+        for j in k..cols {
+            let norm = (k..rows).map(|i| r[i * cols + j].powi(2)).sum::<f64>();
+            if norm > p_norm {
+                p = j;
+                p_norm = norm;
+            }
+        }
+        if p != k {
+            for i in 0..rows {
+                r.swap(i * cols + k, i * cols + p);
+            }
+            perm.swap(k, p);
+        }
+
+        let mut alpha = p_norm.sqrt();
+        if r[k * cols + k] > 0.0 {
+            alpha = -alpha;
+        }
+        diag[k] = alpha;
+
+        // Householder vector v = x - alpha e_1, applied as I - 2 v vᵀ / vᵀv
+        let mut v = (k..rows).map(|i| r[i * cols + k]).collect::<Vec<f64>>();
+        v[0] -= alpha;
+        let v_norm = v.iter().map(|x| x * x).sum::<f64>();
+        if v_norm == 0.0 {
+            continue;
+        }
+
+        for j in k..cols {
+            let s = (k..rows).map(|i| v[i - k] * r[i * cols + j]).sum::<f64>();
+            let f = 2.0 * s / v_norm;
+            for i in k..rows {
+                r[i * cols + j] -= f * v[i - k];
+            }
+        }
+        let s = (k..rows).map(|i| v[i - k] * qtb[i]).sum::<f64>();
+        let f = 2.0 * s / v_norm;
+        for i in k..rows {
+            qtb[i] -= f * v[i - k];
+        }
+    }
+
+    let tol = f64::EPSILON * rows as f64 * diag[0].abs();
+    if diag.iter().any(|d| d.is_nan() || d.abs() <= tol) {
+        return Err(Error::SingularSystem);
+    }
+
+    let mut z = vec![0.0; cols];
+    for i in (0..cols).rev() {
+        let mut s = qtb[i];
+        for j in i + 1..cols {
+            s -= r[i * cols + j] * z[j];
+        }
+        z[i] = s / diag[i];
+    }
+
+    let mut x = vec![0.0; cols];
+    for k in 0..cols {
+        x[perm[k]] = z[k];
+    }
+    Ok(x)
+}
+
+/// Least-squares solution of a banded system by QR, with a Givens rotation per entry. Each row
+/// has its non-zero entries in `bandwidth` consecutive columns, and rows are added in order of
+/// their first column, so that the triangular factor keeps the bandwidth.
+#[derive(Debug, Default)]
+pub(crate) struct BandedQr {
+    cols: usize,
+    bandwidth: usize,
+    rows: usize,
+    /// Row `j` of the triangular factor from its diagonal on, `bandwidth` entries per row.
+    r: Vec<f64>,
+    qtb: Vec<f64>,
+    row: Vec<f64>,
+}
+
+impl BandedQr {
+    pub(crate) fn with_capacity(cols: usize, bandwidth: usize) -> Self {
+        BandedQr {
+            r: Vec::with_capacity(cols * bandwidth),
+            qtb: Vec::with_capacity(cols),
+            row: Vec::with_capacity(bandwidth),
+            ..BandedQr::default()
+        }
+    }
+
+    /// Starts a system of `cols` unknowns.
+    pub(crate) fn reset(&mut self, cols: usize, bandwidth: usize) {
+        self.cols = cols;
+        self.bandwidth = bandwidth;
+        self.rows = 0;
+        self.r.clear();
+        self.r.resize(cols * bandwidth, 0.0);
+        self.qtb.clear();
+        self.qtb.resize(cols, 0.0);
+    }
+
+    /// Adds the row with `values` in the columns from `first` on and right-hand side `b`.
+    pub(crate) fn add_row(&mut self, first: usize, values: &[f64], b: f64) {
+        let w = self.bandwidth;
+        let row = &mut self.row;
+        row.clear();
+        row.extend_from_slice(values);
+        row.resize(w, 0.0);
+        let mut b = b;
+        self.rows += 1;
+
+        // rotate the row into the factor one column at a time
+        for i in 0..w {
+            let j = first + i;
+            if j >= self.cols || row[i] == 0.0 {
+                continue;
+            }
+            let pivot = &mut self.r[j * w..(j + 1) * w];
+            let h = (pivot[0] * pivot[0] + row[i] * row[i]).sqrt();
+            let (c, s) = (pivot[0] / h, row[i] / h);
+            pivot[0] = h;
+            for k in i + 1..w {
+                let (p, q) = (pivot[k - i], row[k]);
+                pivot[k - i] = c * p + s * q;
+                row[k] = c * q - s * p;
+            }
+            let q = self.qtb[j];
+            self.qtb[j] = c * q + s * b;
+            b = c * b - s * q;
+        }
+    }
+
+    /// Solves the system into `x`. Fails if a diagonal entry of the factor is negligible.
+    pub(crate) fn solve_into(&self, x: &mut Vec<f64>) -> Result<(), Error> {
+        let (n, w) = (self.cols, self.bandwidth);
+        let max_diag = (0..n).map(|j| self.r[j * w]).fold(0.0, f64::max);
+        let tol = f64::EPSILON * self.rows as f64 * max_diag;
+
+        x.clear();
+        x.resize(n, 0.0);
+        for j in (0..n).rev() {
+            let d = self.r[j * w];
+            if d.is_nan() || d <= tol {
+                return Err(Error::SingularSystem);
+            }
+            let mut s = self.qtb[j];
+            for k in 1..w.min(n - j) {
+                s -= self.r[j * w + k] * x[j + k];
+            }
+            x[j] = s / d;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+//! Float functions from `libm` for builds without `std`, under the names of the inherent `f64`
+//! methods they stand in for.
+
+pub(crate) trait Float {
+    fn ceil(self) -> f64;
+    fn cos(self) -> f64;
+    fn exp(self) -> f64;
+    fn floor(self) -> f64;
+    fn ln(self) -> f64;
+    fn log2(self) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn round(self) -> f64;
+    fn sin(self) -> f64;
+    fn sqrt(self) -> f64;
+}
+
+impl Float for f64 {
+    fn ceil(self) -> f64 {
+        libm::ceil(self)
+    }
+
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+
+    fn exp(self) -> f64 {
+        libm::exp(self)
+    }
+
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+
+    fn ln(self) -> f64 {
+        libm::log(self)
+    }
+
+    fn log2(self) -> f64 {
+        libm::log2(self)
+    }
+
+    fn powf(self, n: f64) -> f64 {
+        libm::pow(self, n)
+    }
+
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n as f64)
+    }
+
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{FEATURE_NAMES, N_CATCH22, N_CATCH24, N_FEATURES};
+
+/// Unit of time in which the values of an interval series (e.g. RR intervals) are expressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TimeUnit {
+    #[default]
+    Seconds,
+    Milliseconds,
+}
+
+impl TimeUnit {
+    pub fn to_millis(self, value: f64) -> f64 {
+        match self {
+            TimeUnit::Seconds => value * 1000.0,
+            TimeUnit::Milliseconds => value,
+        }
+    }
+}
+
+/// Unit in which lag-based features are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum LagUnit {
+    #[default]
+    Samples,
+    /// Lags are divided by the sampling rate. Without a sampling rate this is the same as
+    /// [`LagUnit::Samples`].
+    Seconds,
+}
+
+/// Which features of [`FEATURE_NAMES`](crate::FEATURE_NAMES) [`compute_all`](crate::compute_all)
+/// returns. Each mode is a prefix of the list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FeatureMode {
+    /// The 22 catch22 features.
+    Catch22,
+    /// catch22 followed by `DN_Mean` and `DN_Spread_Std`, as pycatch22 with `catch24=True`.
+    Catch24,
+    /// catch24 followed by `SY_Slope`.
+    #[default]
+    Catch24PlusSlope,
+}
+
+impl FeatureMode {
+    /// Number of features in the mode.
+    pub fn n_features(self) -> usize {
+        match self {
+            FeatureMode::Catch22 => N_CATCH22,
+            FeatureMode::Catch24 => N_CATCH24,
+            FeatureMode::Catch24PlusSlope => N_FEATURES,
+        }
+    }
+
+    /// Names of the features in the mode, in order.
+    pub fn names(self) -> &'static [&'static str] {
+        &FEATURE_NAMES[..self.n_features()]
+    }
+}
+
+/// Parameters of the entropy features added to [`compute_all`](crate::compute_all).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EntropyOptions {
+    /// Embedding dimension of sample, approximate and multiscale entropy.
+    pub m: usize,
+    /// Matching tolerance as a multiple of the standard deviation of the series.
+    pub tolerance: f64,
+    /// Pattern length of permutation entropy.
+    pub order: usize,
+    /// Delay between the values of a permutation pattern.
+    pub delay: usize,
+    /// Largest coarse-graining scale of multiscale entropy.
+    pub max_scale: usize,
+}
+
+impl Default for EntropyOptions {
+    fn default() -> Self {
+        EntropyOptions {
+            m: 2,
+            tolerance: 0.2,
+            order: 3,
+            delay: 1,
+            max_scale: 5,
+        }
+    }
+}
+
+/// Options controlling how features are extracted.
+///
+/// The defaults reproduce the reference catch22 implementation, which treats the series as
+/// unit-rate samples whose values are in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Options {
+    /// Sampling rate of the series in Hz. When set, spectral features are reported in Hz
+    /// instead of rad/sample.
+    pub sampling_rate: Option<f64>,
+    /// Unit of the values of the series, used to interpret the pNN threshold in milliseconds.
+    pub value_unit: TimeUnit,
+    /// Unit of `co_f1ecac`, `co_first_min_ac` and `pd_periodicity_wang_th0_01`.
+    pub lag_unit: LagUnit,
+    /// Features returned by [`compute_all`](crate::compute_all); all of them by default.
+    pub mode: FeatureMode,
+    /// Entropy features computed by [`compute_all`](crate::compute_all) in addition to catch22.
+    pub entropy: Option<EntropyOptions>,
+}
+
+impl Options {
+    pub fn with_sampling_rate(mut self, fs: f64) -> Self {
+        self.sampling_rate = Some(fs);
+        self
+    }
+
+    pub fn with_value_unit(mut self, unit: TimeUnit) -> Self {
+        self.value_unit = unit;
+        self
+    }
+
+    pub fn with_lag_unit(mut self, unit: LagUnit) -> Self {
+        self.lag_unit = unit;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: FeatureMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_entropy(mut self, entropy: EntropyOptions) -> Self {
+        self.entropy = Some(entropy);
+        self
+    }
+
+    /// Converts a lag in samples to the configured lag unit.
+    pub fn lag(&self, samples: f64) -> f64 {
+        match (self.lag_unit, self.sampling_rate) {
+            (LagUnit::Seconds, Some(fs)) => samples / fs,
+            _ => samples,
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Timing of threshold exceedances, as used by the `DN_OutlierInclude` features.
+//!
+//! For thresholds rising in steps of `increment` from 0 to the maximum, the indices of the values
+//! at or above the threshold are summarised by the mean interval between them, their number as
+//! a percentage of the non-negative values, and their median position relative to the middle of
+//! the series. The feature is the median of the relative positions over the thresholds that keep
+//! more than `trim_threshold` percent of the values.
+//!
+//! The values are sorted once and the thresholds swept downwards, adding exceedances to a Fenwick
+//! tree over positions so that each median is an order-statistic query.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::quantile::{median, median_in_place};
+use crate::statistics::{is_constant, max_, mean, zscore_into};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// Exceedances above the thresholds (`DN_OutlierInclude_p`).
+    #[default]
+    Positive,
+    /// Exceedances below the negated thresholds (`DN_OutlierInclude_n`).
+    Negative,
+}
+
+/// Treatment of series that are not z-scored. The thresholds are absolute, so on raw-scale data
+/// both the result and the number of thresholds depend on the units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Normalization {
+    /// Z-score the series unless its mean is already 0 and its standard deviation 1.
+    #[default]
+    Auto,
+    /// Reject series that are not z-scored.
+    Require,
+    /// Use the values as they are.
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutlierConfig {
+    /// Step between successive thresholds.
+    pub increment: f64,
+    /// Minimum percentage of exceedances for a threshold to contribute to the result.
+    pub trim_threshold: f64,
+    pub normalization: Normalization,
+}
+
+impl Default for OutlierConfig {
+    /// The settings of `DN_OutlierInclude_*_001_mdrmd`.
+    fn default() -> Self {
+        OutlierConfig {
+            increment: 0.01,
+            trim_threshold: 2.0,
+            normalization: Normalization::Auto,
+        }
+    }
+}
+
+impl OutlierConfig {
+    pub fn with_increment(mut self, increment: f64) -> Self {
+        self.increment = increment;
+        self
+    }
+
+    pub fn with_trim_threshold(mut self, trim_threshold: f64) -> Self {
+        self.trim_threshold = trim_threshold;
+        self
+    }
+
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+}
+
+/// Exceedance statistics at each threshold `i * increment`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlierSweep {
+    pub thresholds: Vec<f64>,
+    /// Mean number of samples between consecutive exceedances, 0 if there are fewer than two.
+    pub mean_interval: Vec<f64>,
+    /// Number of intervals between exceedances as a percentage of the non-negative values.
+    pub percent: Vec<f64>,
+    /// Median index of the exceedances relative to the middle of the series, in [-1, 1].
+    pub median_position: Vec<f64>,
+    pub trim_threshold: f64,
+}
+
+impl OutlierSweep {
+    /// Index of the last threshold whose exceedances exceed the trimming percentage.
+    pub fn trim_limit(&self) -> usize {
+        self.percent
+            .iter()
+            .rposition(|&p| p > self.trim_threshold)
+            .unwrap_or(0)
+    }
+
+    /// Median of the relative positions up to the trimming limit, 0 if no threshold was swept.
+    pub fn median_position(&self) -> f64 {
+        if self.median_position.is_empty() {
+            return 0.0;
+        }
+        median(&self.median_position[..self.trim_limit() + 1])
+    }
+}
+
+/// Counts over positions `1..=n` supporting insertion and k-th smallest queries.
+struct Fenwick<'a> {
+    tree: &'a mut [usize],
+}
+
+impl<'a> Fenwick<'a> {
+    /// A tree over the positions `1..tree.len()`, empty if `tree` is zeroed.
+    fn new(tree: &'a mut [usize]) -> Self {
+        Fenwick { tree }
+    }
+
+    fn insert(&mut self, pos: usize) {
+        let mut i = pos;
+        while i < self.tree.len() {
+            self.tree[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Smallest position with `k` inserted positions at or below it.
+    fn kth(&self, k: usize) -> usize {
+        let mut pos = 0;
+        let mut remaining = k;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            let next = pos + step;
+            if next < self.tree.len() && self.tree[next] < remaining {
+                pos = next;
+                remaining -= self.tree[next];
+            }
+            step >>= 1;
+        }
+        pos + 1
+    }
+}
+
+fn is_zscored(a: &[f64]) -> bool {
+    let tol = 1e-6;
+    let m = mean(a);
+    let ss = a.iter().map(|x| (x - m).powi(2)).sum::<f64>();
+    let sd_population = (ss / a.len() as f64).sqrt();
+    let sd_sample = (ss / (a.len() - 1) as f64).sqrt();
+
+    m.abs() < tol && ((sd_population - 1.0).abs() < tol || (sd_sample - 1.0).abs() < tol)
+}
+
+/// Reusable buffers for the threshold sweep.
+#[derive(Debug, Default)]
+pub(crate) struct SweepScratch {
+    /// The normalised series, negated for [`Direction::Negative`].
+    series: Vec<f64>,
+    /// Indices of the values in descending order.
+    order: Vec<usize>,
+    tree: Vec<usize>,
+    median_position: Vec<f64>,
+}
+
+impl SweepScratch {
+    /// Buffers for z-scored series up to `len` values.
+    pub(crate) fn with_capacity(len: usize, config: &OutlierConfig) -> Self {
+        // a z-scored series of n values lies within ±√n
+        let n_thresh = ((len as f64).sqrt() / config.increment) as usize + 2;
+        SweepScratch {
+            series: Vec::with_capacity(len),
+            order: Vec::with_capacity(len),
+            tree: Vec::with_capacity(len + 1),
+            median_position: Vec::with_capacity(n_thresh),
+        }
+    }
+}
+
+/// Validates and normalises the series into `scratch` and sorts it, returning the number of
+/// thresholds. `ascending`, if given, holds the indices that sort `a` in ascending order.
+fn prepare(
+    a: &[f64],
+    direction: Direction,
+    config: &OutlierConfig,
+    ascending: Option<&[usize]>,
+    scratch: &mut SweepScratch,
+) -> Result<usize, Error> {
+    if config.increment.is_nan() || config.increment <= 0.0 {
+        return Err(Error::InvalidParameter("increment must be positive"));
+    }
+    if a.len() < 2 {
+        return Err(Error::TooShort {
+            len: a.len(),
+            min: 2,
+        });
+    }
+    if a.iter().any(|x| x.is_nan()) {
+        return Err(Error::MissingValues);
+    }
+    if is_constant(a) {
+        return Err(Error::ConstantSeries);
+    }
+
+    let series = &mut scratch.series;
+    match config.normalization {
+        Normalization::Auto if !is_zscored(a) => zscore_into(a, series),
+        Normalization::Require if !is_zscored(a) => return Err(Error::NotNormalized),
+        _ => {
+            series.clear();
+            series.extend_from_slice(a);
+        }
+    }
+    if direction == Direction::Negative {
+        series.iter_mut().for_each(|x| *x = -*x);
+    }
+
+    let inc = config.increment;
+    let max_val = max_(series);
+
+    // normalising keeps the order, up to ties, which the sweep passes together
+    let order = &mut scratch.order;
+    order.clear();
+    match ascending {
+        Some(ascending) if direction == Direction::Negative => order.extend_from_slice(ascending),
+        Some(ascending) => order.extend(ascending.iter().rev()),
+        None => {
+            order.extend(0..series.len());
+            order.sort_unstable_by(|&i, &j| series[j].total_cmp(&series[i]));
+        }
+    }
+
+    Ok(if max_val < inc {
+        0
+    } else {
+        ((max_val / inc) + 1.0) as usize
+    })
+}
+
+/// Sweeps the thresholds downwards, passing the index of each with its mean interval, percentage
+/// and median position to `visit`.
+fn sweep(
+    scratch: &mut SweepScratch,
+    increment: f64,
+    n_thresh: usize,
+    mut visit: impl FnMut(usize, f64, f64, f64),
+) {
+    let a = &scratch.series;
+    let order = &scratch.order;
+    let n = a.len();
+    let tot = a.iter().filter(|&x| *x >= 0.0).count();
+
+    scratch.tree.clear();
+    scratch.tree.resize(n + 1, 0);
+    let mut exceedances = Fenwick::new(&mut scratch.tree);
+    let mut high_size = 0;
+    // 1-based indices of the first and last exceedance
+    let mut first = n + 1;
+    let mut last = 0;
+
+    for i in (0..n_thresh).rev() {
+        let th = i as f64 * increment;
+        while high_size < n && a[order[high_size]] >= th {
+            let pos = order[high_size] + 1;
+            exceedances.insert(pos);
+            first = first.min(pos);
+            last = last.max(pos);
+            high_size += 1;
+        }
+
+        let med = if high_size == 0 {
+            0.0
+        } else if high_size.is_multiple_of(2) {
+            let upper = exceedances.kth(high_size / 2 + 1) as f64;
+            let lower = exceedances.kth(high_size / 2) as f64;
+            (upper + lower) / 2.0
+        } else {
+            exceedances.kth(high_size / 2 + 1) as f64
+        };
+
+        // the intervals telescope to the span between the first and last exceedance
+        let mean_interval = if high_size > 1 {
+            (last - first) as f64 / (high_size - 1) as f64
+        } else {
+            0.0
+        };
+        let percent = ((high_size.saturating_sub(1)) as f64 * 100.0) / tot as f64;
+        visit(i, mean_interval, percent, med / (n as f64 / 2.0) - 1.0);
+    }
+}
+
+pub fn outlier_sweep(
+    a: &[f64],
+    direction: Direction,
+    config: &OutlierConfig,
+) -> Result<OutlierSweep, Error> {
+    let mut scratch = SweepScratch::default();
+    let n_thresh = prepare(a, direction, config, None, &mut scratch)?;
+
+    let inc = config.increment;
+    let mut out = OutlierSweep {
+        thresholds: (0..n_thresh).map(|i| i as f64 * inc).collect(),
+        mean_interval: vec![0.0; n_thresh],
+        percent: vec![0.0; n_thresh],
+        median_position: vec![0.0; n_thresh],
+        trim_threshold: config.trim_threshold,
+    };
+
+    sweep(
+        &mut scratch,
+        inc,
+        n_thresh,
+        |i, interval, percent, position| {
+            out.mean_interval[i] = interval;
+            out.percent[i] = percent;
+            out.median_position[i] = position;
+        },
+    );
+
+    Ok(out)
+}
+
+/// [`OutlierSweep::median_position`] of the sweep, computed in reusable buffers, optionally
+/// from the indices that sort `a` in ascending order.
+pub(crate) fn median_position_with(
+    a: &[f64],
+    direction: Direction,
+    config: &OutlierConfig,
+    ascending: Option<&[usize]>,
+    scratch: &mut SweepScratch,
+) -> Result<f64, Error> {
+    let n_thresh = prepare(a, direction, config, ascending, scratch)?;
+    if n_thresh == 0 {
+        return Ok(0.0);
+    }
+
+    let mut positions = core::mem::take(&mut scratch.median_position);
+    positions.clear();
+    positions.resize(n_thresh, 0.0);
+
+    // the thresholds are visited downwards, so the trimming limit is the first to exceed it
+    let mut trim_limit = None;
+    sweep(
+        scratch,
+        config.increment,
+        n_thresh,
+        |i, _, percent, position| {
+            positions[i] = position;
+            if trim_limit.is_none() && percent > config.trim_threshold {
+                trim_limit = Some(i);
+            }
+        },
+    );
+
+    let med = median_in_place(&mut positions[..trim_limit.unwrap_or(0) + 1]);
+    scratch.median_position = positions;
+    Ok(med)
+}
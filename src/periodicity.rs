@@ -0,0 +1,274 @@
+//! Periodicity detection from the peaks of the autocovariance of a detrended series (Wang et al.,
+//! 2007), as used by `PD_PeriodicityWang_th0_01`, with a cross-check against the peak of the
+//! periodogram.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::spline::{BSpline, SplineScratch, splinefit_with};
+use crate::statistics::{autocov_lag, is_constant, linreg, mean, welch};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Detrend {
+    None,
+    /// Subtract the mean.
+    Constant,
+    /// Subtract the least-squares line.
+    Linear,
+    /// Subtract a cubic spline of two pieces with a break at the middle of the series.
+    #[default]
+    Spline,
+    /// Subtract a least-squares spline with evenly spaced breaks.
+    BSpline {
+        degree: usize,
+        n_breaks: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeriodicityConfig {
+    /// Minimum difference between a peak and the preceding trough of the autocovariance.
+    pub threshold: f64,
+    pub detrend: Detrend,
+    /// Largest lag searched, `ceil(n / 3)` when `None`.
+    pub max_lag: Option<usize>,
+}
+
+impl Default for PeriodicityConfig {
+    /// The settings of `PD_PeriodicityWang_th0_01`.
+    fn default() -> Self {
+        PeriodicityConfig {
+            threshold: 0.01,
+            detrend: Detrend::Spline,
+            max_lag: None,
+        }
+    }
+}
+
+impl PeriodicityConfig {
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn with_detrend(mut self, detrend: Detrend) -> Self {
+        self.detrend = detrend;
+        self
+    }
+
+    pub fn with_max_lag(mut self, max_lag: usize) -> Self {
+        self.max_lag = Some(max_lag);
+        self
+    }
+}
+
+/// A peak of the autocovariance that is non-negative and rises at least `threshold` above the
+/// trough before it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candidate {
+    /// Lag of the peak in samples.
+    pub period: usize,
+    /// Autocovariance at the peak.
+    pub peak: f64,
+    /// Lag of the nearest trough before the peak.
+    pub trough_lag: usize,
+    /// Autocovariance at that trough.
+    pub trough: f64,
+}
+
+impl Candidate {
+    pub fn prominence(&self) -> f64 {
+        self.peak - self.trough
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Periodicity {
+    /// Qualifying peaks in order of increasing lag.
+    pub candidates: Vec<Candidate>,
+    /// Period in samples at the maximum of the periodogram of the detrended series, excluding
+    /// the zero frequency.
+    pub spectral_period: Option<f64>,
+}
+
+impl Periodicity {
+    /// The first qualifying period, as reported by the Wang method.
+    pub fn period(&self) -> Option<usize> {
+        self.candidates.first().map(|c| c.period)
+    }
+
+    /// Whether the first period lies within the relative `tolerance` of the spectral period.
+    pub fn agrees_with_spectrum(&self, tolerance: f64) -> bool {
+        match (self.period(), self.spectral_period) {
+            (Some(p), Some(s)) => ((p as f64 - s) / s).abs() <= tolerance,
+            _ => false,
+        }
+    }
+}
+
+pub fn detrend(a: &[f64], method: Detrend) -> Result<Vec<f64>, Error> {
+    let mut y = Vec::new();
+    detrend_into(a, method, &mut y, &mut SplineScratch::default())?;
+    Ok(y)
+}
+
+fn detrend_into(
+    a: &[f64],
+    method: Detrend,
+    y: &mut Vec<f64>,
+    spline: &mut SplineScratch,
+) -> Result<(), Error> {
+    y.clear();
+    match method {
+        Detrend::None => y.extend_from_slice(a),
+        Detrend::Constant => {
+            let m = mean(a);
+            y.extend(a.iter().map(|x| x - m));
+        }
+        Detrend::Linear => {
+            // the sample times, replaced by the residuals
+            y.extend((0..a.len()).map(|i| i as f64));
+            let (m, b) = linreg(a.len(), y, a);
+            for (t, x) in y.iter_mut().zip(a) {
+                *t = x - (m * *t + b);
+            }
+        }
+        Detrend::Spline => {
+            splinefit_with(a, spline)?;
+            y.extend(a.iter().enumerate().map(|(i, x)| x - spline.eval(i as f64)));
+        }
+        Detrend::BSpline { degree, n_breaks } => {
+            y.extend(BSpline::fit(a, degree, n_breaks)?.residuals(a));
+        }
+    }
+    Ok(())
+}
+
+fn spectral_period(y: &[f64]) -> Option<f64> {
+    let window = vec![1.0; y.len()];
+    let (s, f) = welch(y, 1.0, &window);
+
+    let mut i_max = None;
+    for i in 1..s.len() {
+        if s[i] > 0.0 && i_max.is_none_or(|m: usize| s[i] > s[m]) {
+            i_max = Some(i);
+        }
+    }
+    i_max.map(|i| 1.0 / f[i])
+}
+
+/// Reusable buffers for the periodicity analysis.
+#[derive(Debug, Default)]
+pub(crate) struct PeriodicityScratch {
+    detrended: Vec<f64>,
+    /// Autocovariance at lags `1..=max_lag`.
+    acf: Vec<f64>,
+    spline: SplineScratch,
+}
+
+impl PeriodicityScratch {
+    /// Buffers for series up to `len` values detrended with [`Detrend::Spline`].
+    pub(crate) fn with_capacity(len: usize) -> Self {
+        PeriodicityScratch {
+            detrended: Vec::with_capacity(len),
+            acf: Vec::with_capacity(len.div_ceil(3)),
+            spline: SplineScratch::with_capacity(3, 3),
+        }
+    }
+}
+
+/// Detrends the series and computes its autocovariance into `scratch`.
+fn autocovariance_into(
+    a: &[f64],
+    config: &PeriodicityConfig,
+    scratch: &mut PeriodicityScratch,
+) -> Result<(), Error> {
+    if a.len() < 3 {
+        return Err(Error::TooShort {
+            len: a.len(),
+            min: 3,
+        });
+    }
+    if is_constant(a) {
+        return Err(Error::ConstantSeries);
+    }
+
+    let y_sub = &mut scratch.detrended;
+    detrend_into(a, config.detrend, y_sub, &mut scratch.spline)?;
+
+    let ac_max = config
+        .max_lag
+        .unwrap_or((a.len() as f64 / 3.0).ceil() as usize)
+        .min(a.len() - 1);
+    if ac_max < 3 {
+        return Err(Error::InvalidParameter("max_lag must be at least 3"));
+    }
+
+    // acf[i] is the autocovariance at lag i + 1
+    scratch.acf.clear();
+    scratch
+        .acf
+        .extend((1..(ac_max + 1)).map(|i| autocov_lag(y_sub, i)));
+    Ok(())
+}
+
+/// Qualifying peaks of the autocovariance in order of increasing lag, each paired with the last
+/// trough before it.
+fn candidates(acf: &[f64], threshold: f64) -> impl Iterator<Item = Candidate> + '_ {
+    let mut last_trough = None;
+
+    (1..acf.len() - 1).filter_map(move |i| {
+        let slope_in = acf[i] - acf[i - 1];
+        let slope_out = acf[i + 1] - acf[i];
+
+        let is_trough = slope_in < 0.0 && slope_out > 0.0;
+        let is_peak = slope_in > 0.0 && slope_out < 0.0;
+        if is_trough {
+            last_trough = Some(i);
+        }
+        if !is_peak {
+            return None;
+        }
+
+        let the_peak = acf[i];
+        let i_trough = last_trough?;
+        let the_trough = acf[i_trough];
+
+        if the_peak - the_trough < threshold || the_peak < 0.0 {
+            return None;
+        }
+
+        Some(Candidate {
+            period: i + 1,
+            peak: the_peak,
+            trough_lag: i_trough + 1,
+            trough: the_trough,
+        })
+    })
+}
+
+pub fn periodicity(a: &[f64], config: &PeriodicityConfig) -> Result<Periodicity, Error> {
+    let mut scratch = PeriodicityScratch::default();
+    autocovariance_into(a, config, &mut scratch)?;
+
+    Ok(Periodicity {
+        candidates: candidates(&scratch.acf, config.threshold).collect(),
+        spectral_period: spectral_period(&scratch.detrended),
+    })
+}
+
+/// [`Periodicity::period`] of the analysis, computed in reusable buffers and without the
+/// spectral cross-check.
+pub(crate) fn period_with(
+    a: &[f64],
+    config: &PeriodicityConfig,
+    scratch: &mut PeriodicityScratch,
+) -> Result<Option<usize>, Error> {
+    autocovariance_into(a, config, scratch)?;
+    Ok(candidates(&scratch.acf, config.threshold)
+        .next()
+        .map(|c| c.period))
+}
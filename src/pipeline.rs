@@ -0,0 +1,263 @@
+//! Declarative extraction pipelines behind the `pipeline` feature.
+//!
+//! A [`PipelineSpec`] describes the preprocessing of each window, the windowing, the features and
+//! their options, and is read from TOML or JSON:
+//!
+//! ```toml
+//! preprocess = [
+//!     { step = "missing", strategy = "interpolate" },
+//!     { step = "detrend" },
+//!     { step = "zscore" },
+//! ]
+//!
+//! [window]
+//! length = 500
+//! step = 250
+//!
+//! [features]
+//! names = ["CO_f1ecac", "CO_FirstMin_ac"]
+//! keywords = ["spectral"]
+//!
+//! [options]
+//! sampling_rate = 250.0
+//! ```
+//!
+//! Every section is optional: by default the whole series is used as it is, with every feature
+//! of [`FEATURE_NAMES`](crate::FEATURE_NAMES) and the default [`Options`].
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::statistics::linreg;
+use crate::{
+    Catch22Workspace, Error, Feature, FeatureMode, FeatureSet, Features, Options, compute_set_in,
+    push_entropy, zscore,
+};
+
+/// A feature extraction pipeline.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PipelineSpec {
+    /// Steps applied to each window, in order.
+    pub preprocess: Vec<Step>,
+    /// Windows to extract features from; the whole series if `None`.
+    pub window: Option<Window>,
+    pub features: Selection,
+    pub options: Options,
+}
+
+/// A preprocessing step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Step {
+    /// Handles NaN values. Without this step a window with NaN values is rejected.
+    Missing { strategy: Missing },
+    /// Subtracts the least-squares line.
+    Detrend,
+    /// Subtracts the mean and divides by the standard deviation, as [`zscore`].
+    #[serde(rename = "zscore")]
+    ZScore,
+}
+
+/// Treatment of NaN values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Missing {
+    /// Fails with [`Error::MissingValues`].
+    #[default]
+    Reject,
+    /// Removes them, shortening the window. Features that need more values than remain are NaN.
+    Drop,
+    /// Interpolates linearly between the neighbouring values, repeating the first or last value
+    /// at the ends.
+    Interpolate,
+}
+
+/// Sliding windows over the series. Only complete windows are used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Window {
+    pub length: usize,
+    /// Offset between the starts of consecutive windows; `length` if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<usize>,
+}
+
+impl Window {
+    pub fn new(length: usize) -> Self {
+        Window { length, step: None }
+    }
+
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = Some(step);
+        self
+    }
+}
+
+/// The features to compute: those named or tagged with one of the keywords, or those of `mode`
+/// if neither is given.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Selection {
+    pub mode: FeatureMode,
+    /// Long or short feature names.
+    pub names: FeatureSet,
+    /// Keywords of [`FeatureInfo`](crate::FeatureInfo).
+    pub keywords: Vec<String>,
+}
+
+impl Selection {
+    pub fn features(&self) -> FeatureSet {
+        if self.names.is_empty() && self.keywords.is_empty() {
+            return self.mode.into();
+        }
+        self.keywords.iter().fold(self.names, |set, keyword| {
+            set | FeatureSet::from_keyword(keyword)
+        })
+    }
+}
+
+/// The features of one window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowFeatures {
+    /// Index of the first value of the window in the series.
+    pub start: usize,
+    /// Number of values in the window before preprocessing.
+    pub length: usize,
+    pub features: Features,
+}
+
+impl PipelineSpec {
+    pub fn from_toml(spec: &str) -> Result<Self, Error> {
+        let spec: PipelineSpec =
+            toml::from_str(spec).map_err(|e| Error::InvalidSpec(e.message().to_string()))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn from_json(spec: &str) -> Result<Self, Error> {
+        let spec: PipelineSpec =
+            serde_json::from_str(spec).map_err(|e| Error::InvalidSpec(e.to_string()))?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Checks the window and the keywords.
+    pub fn validate(&self) -> Result<(), Error> {
+        if let Some(window) = self.window {
+            if window.length == 0 {
+                return Err(Error::InvalidParameter("window length must be at least 1"));
+            }
+            if window.step == Some(0) {
+                return Err(Error::InvalidParameter("window step must be at least 1"));
+            }
+        }
+        for keyword in &self.features.keywords {
+            if !Feature::ALL.iter().any(|f| f.info().has_keyword(keyword)) {
+                return Err(Error::InvalidSpec(format!("unknown keyword: {keyword}")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extracts the features of every window of `series`.
+    pub fn run(&self, series: &[f64]) -> Result<Vec<WindowFeatures>, Error> {
+        self.validate()?;
+
+        let (length, step) = match self.window {
+            Some(window) => (window.length, window.step.unwrap_or(window.length)),
+            None => (series.len(), series.len()),
+        };
+        if series.is_empty() || series.len() < length {
+            return Err(Error::TooShort {
+                len: series.len(),
+                min: length.max(1),
+            });
+        }
+
+        let set = self.features.features();
+        let mut workspace = Catch22Workspace::new();
+        let mut window = Vec::with_capacity(length);
+        let mut out = Vec::new();
+
+        for start in (0..=series.len() - length).step_by(step) {
+            window.clear();
+            window.extend_from_slice(&series[start..start + length]);
+            for step in &self.preprocess {
+                step.apply(&mut window)?;
+            }
+            if window.is_empty() || window.iter().any(|x| x.is_nan()) {
+                return Err(Error::MissingValues);
+            }
+
+            let mut features = compute_set_in(&mut workspace, &window, &set, &self.options);
+            if let Some(en) = self.options.entropy {
+                push_entropy(&mut features, &window, &en);
+            }
+            out.push(WindowFeatures {
+                start,
+                length,
+                features,
+            });
+        }
+        Ok(out)
+    }
+}
+
+impl Step {
+    fn apply(&self, x: &mut Vec<f64>) -> Result<(), Error> {
+        match self {
+            Step::Missing { strategy } => match strategy {
+                Missing::Reject if x.iter().any(|v| v.is_nan()) => {
+                    return Err(Error::MissingValues);
+                }
+                Missing::Reject => {}
+                Missing::Drop => x.retain(|v| !v.is_nan()),
+                Missing::Interpolate => interpolate(x)?,
+            },
+            Step::Detrend => {
+                let t = (1..=x.len()).map(|i| i as f64).collect::<Vec<f64>>();
+                let (m, b) = linreg(x.len(), &t, x);
+                for (v, t) in x.iter_mut().zip(&t) {
+                    *v -= m * t + b;
+                }
+            }
+            Step::ZScore => *x = zscore(x),
+        }
+        Ok(())
+    }
+}
+
+/// Replaces NaN values by linear interpolation between their neighbours.
+fn interpolate(x: &mut [f64]) -> Result<(), Error> {
+    let mut previous: Option<usize> = None;
+    for i in 0..x.len() {
+        let value = x[i];
+        if value.is_nan() {
+            continue;
+        }
+        match previous {
+            // leading gap
+            None => x[..i].fill(value),
+            Some(p) if p + 1 < i => {
+                let a = x[p];
+                for j in p + 1..i {
+                    x[j] = a + (value - a) * (j - p) as f64 / (i - p) as f64;
+                }
+            }
+            Some(_) => {}
+        }
+        previous = Some(i);
+    }
+    match previous {
+        Some(p) => {
+            let last = x[p];
+            x[p + 1..].fill(last);
+            Ok(())
+        }
+        None => Err(Error::MissingValues),
+    }
+}
@@ -0,0 +1,173 @@
+//! Sample quantiles under the nine definitions of Hyndman & Fan (1996) and the definition used
+//! by MATLAB's `quantile`, which catch22 follows.
+//!
+//! Several quantiles of one series are computed from a single sort; a single quantile or the
+//! median uses selection instead. Probabilities outside [0, 1], empty series and series with
+//! `NaN` values give `NaN`.
+
+use alloc::vec::Vec;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Method {
+    /// Inverse of the empirical CDF.
+    Type1,
+    /// Inverse of the empirical CDF, averaging at discontinuities.
+    Type2,
+    /// Nearest order statistic, ties to even (SAS).
+    Type3,
+    /// Linear interpolation of the empirical CDF.
+    Type4,
+    /// Piecewise linear with knots at the midpoints of the CDF steps.
+    Type5,
+    /// Linear interpolation of the expectations of the order statistics' CDF values (Minitab,
+    /// SPSS).
+    Type6,
+    /// Linear interpolation of the modes of the order statistics (R, NumPy default).
+    Type7,
+    /// Approximately median-unbiased.
+    Type8,
+    /// Approximately unbiased for normal data.
+    Type9,
+    /// MATLAB's definition: the sorted values are placed at probabilities `(i - 0.5) / n` and
+    /// interpolated, with the extremes beyond the first and last of these.
+    #[default]
+    Matlab,
+}
+
+/// 0-based index of the lower order statistic and the interpolation weight of the next one.
+fn position(n: usize, p: f64, method: Method) -> (usize, f64) {
+    let nf = n as f64;
+    // tolerance for p * n landing on an integer, as in R
+    let fuzz = 4.0 * f64::EPSILON;
+
+    let lower = |j: f64| (j.max(1.0).min(nf) as usize) - 1;
+
+    let (j, gamma) = match method {
+        Method::Matlab => {
+            let q = 0.5 / nf;
+            if p < q {
+                return (0, 0.0);
+            } else if p > 1.0 - q {
+                return (n - 1, 0.0);
+            }
+            let quant_idx = nf * p - 0.5;
+            let idx_left = quant_idx.floor();
+            return (idx_left as usize, quant_idx - idx_left);
+        }
+        Method::Type1 | Method::Type2 | Method::Type3 => {
+            let m = if method == Method::Type3 { -0.5 } else { 0.0 };
+            let nppm = nf * p + m;
+            let j = (nppm + fuzz).floor();
+            let exact = (nppm - j).abs() < fuzz;
+            let gamma = match method {
+                Method::Type1 => !exact as u8 as f64,
+                Method::Type2 => {
+                    if exact {
+                        0.5
+                    } else {
+                        1.0
+                    }
+                }
+                _ => (!exact || (j as i64) % 2 == 1) as u8 as f64,
+            };
+            (j, gamma)
+        }
+        _ => {
+            let (alpha, beta) = match method {
+                Method::Type4 => (0.0, 1.0),
+                Method::Type5 => (0.5, 0.5),
+                Method::Type6 => (0.0, 0.0),
+                Method::Type7 => (1.0, 1.0),
+                Method::Type8 => (1.0 / 3.0, 1.0 / 3.0),
+                _ => (3.0 / 8.0, 3.0 / 8.0),
+            };
+            let nppm = alpha + p * (nf + 1.0 - alpha - beta);
+            let j = (nppm + fuzz).floor();
+            let mut h = nppm - j;
+            if h.abs() < fuzz {
+                h = 0.0;
+            }
+            (j, h)
+        }
+    };
+
+    // below the first order statistic only x_1 is used
+    if j < 1.0 {
+        return (0, 0.0);
+    }
+    (lower(j), gamma)
+}
+
+fn interpolate(lo: f64, hi: f64, gamma: f64) -> f64 {
+    if gamma > 0.0 {
+        lo + gamma * (hi - lo)
+    } else {
+        lo
+    }
+}
+
+/// Quantiles of a series that is already sorted in ascending order.
+pub fn quantiles_sorted(sorted: &[f64], p: &[f64], method: Method) -> Vec<f64> {
+    p.iter()
+        .map(|&p| quantile_sorted(sorted, p, method))
+        .collect()
+}
+
+pub(crate) fn quantile_sorted(sorted: &[f64], p: f64, method: Method) -> f64 {
+    let n = sorted.len();
+    // sorted by total order, NaN of either sign is at an end
+    if n == 0 || !(0.0..=1.0).contains(&p) || sorted[0].is_nan() || sorted[n - 1].is_nan() {
+        return f64::NAN;
+    }
+    let (lo, gamma) = position(n, p, method);
+    let hi = (lo + 1).min(n - 1);
+    interpolate(sorted[lo], sorted[hi], gamma)
+}
+
+/// Quantiles at each of the probabilities `p`, sorting the series once.
+pub fn quantiles(a: &[f64], p: &[f64], method: Method) -> Vec<f64> {
+    let mut sorted = a.to_vec();
+    sorted.sort_unstable_by(f64::total_cmp);
+    quantiles_sorted(&sorted, p, method)
+}
+
+/// A single quantile by selection, in O(n) on average.
+pub fn quantile(a: &[f64], p: f64, method: Method) -> f64 {
+    let n = a.len();
+    if n == 0 || !(0.0..=1.0).contains(&p) || a.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let (lo, gamma) = position(n, p, method);
+
+    let mut buffer = a.to_vec();
+    let (_, &mut x_lo, upper) = buffer.select_nth_unstable_by(lo, f64::total_cmp);
+    let x_hi = if gamma > 0.0 && !upper.is_empty() {
+        upper.iter().copied().fold(f64::INFINITY, f64::min)
+    } else {
+        x_lo
+    };
+    interpolate(x_lo, x_hi, gamma)
+}
+
+/// Median by selection: the middle value, or the mean of the two middle values.
+pub fn median(a: &[f64]) -> f64 {
+    median_in_place(&mut a.to_vec())
+}
+
+/// [`median`] reordering `buffer` instead of a copy.
+pub(crate) fn median_in_place(buffer: &mut [f64]) -> f64 {
+    if buffer.is_empty() || buffer.iter().any(|x| x.is_nan()) {
+        return f64::NAN;
+    }
+    let n = buffer.len();
+    let (lower, &mut upper_mid, _) = buffer.select_nth_unstable_by(n / 2, f64::total_cmp);
+    if n.is_multiple_of(2) {
+        let lower_mid = lower.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (upper_mid + lower_mid) / 2.0
+    } else {
+        upper_mid
+    }
+}
@@ -0,0 +1,500 @@
+//! Machine-readable descriptions of the features: names, keywords, parameters, requirements and
+//! origin.
+
+use core::fmt;
+
+use crate::{FEATURE_NAMES, N_FEATURES};
+
+/// A feature of [`FEATURE_NAMES`], in the same order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Feature {
+    OutlierTimingNeg,
+    OutlierTimingPos,
+    Mode5,
+    Mode10,
+    EmbeddingDist,
+    AcfTimescale,
+    AcfFirstMin,
+    Ami2,
+    Trev,
+    WhitenTimescale,
+    ForecastError,
+    AmiTimescale,
+    HighFluctuation,
+    StretchDecreasing,
+    StretchHigh,
+    EntropyPairs,
+    RsRange,
+    Dfa,
+    LowFreqPower,
+    CentroidFreq,
+    TransitionMatrix,
+    Periodicity,
+    Mean,
+    Sd,
+    Slope,
+}
+
+impl Feature {
+    pub const ALL: [Feature; N_FEATURES] = [
+        Feature::OutlierTimingNeg,
+        Feature::OutlierTimingPos,
+        Feature::Mode5,
+        Feature::Mode10,
+        Feature::EmbeddingDist,
+        Feature::AcfTimescale,
+        Feature::AcfFirstMin,
+        Feature::Ami2,
+        Feature::Trev,
+        Feature::WhitenTimescale,
+        Feature::ForecastError,
+        Feature::AmiTimescale,
+        Feature::HighFluctuation,
+        Feature::StretchDecreasing,
+        Feature::StretchHigh,
+        Feature::EntropyPairs,
+        Feature::RsRange,
+        Feature::Dfa,
+        Feature::LowFreqPower,
+        Feature::CentroidFreq,
+        Feature::TransitionMatrix,
+        Feature::Periodicity,
+        Feature::Mean,
+        Feature::Sd,
+        Feature::Slope,
+    ];
+
+    /// Index of the feature in [`FEATURE_NAMES`], as taken by [`compute`](crate::compute).
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(index: usize) -> Option<Feature> {
+        Feature::ALL.get(index).copied()
+    }
+
+    /// The feature with the given long or short name.
+    pub fn from_name(name: &str) -> Option<Feature> {
+        Feature::ALL
+            .into_iter()
+            .find(|f| f.name() == name || f.short_name() == name)
+    }
+
+    /// The hctsa-style name, as in [`FEATURE_NAMES`].
+    pub fn name(self) -> &'static str {
+        FEATURE_NAMES[self.index()]
+    }
+
+    /// The short name used by pycatch22.
+    pub fn short_name(self) -> &'static str {
+        self.info().short_name
+    }
+
+    pub fn info(self) -> &'static FeatureInfo {
+        &INFO[self.index()]
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A fixed parameter of a feature and its value as written in the feature name or the reference
+/// implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub value: &'static str,
+}
+
+/// Description of a feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureInfo {
+    pub feature: Feature,
+    pub name: &'static str,
+    pub short_name: &'static str,
+    pub description: &'static str,
+    /// Lower-case keywords for grouping and filtering, e.g. `"distribution"`, `"correlation"` or
+    /// `"spectral"`.
+    pub keywords: &'static [&'static str],
+    pub parameters: &'static [Parameter],
+    /// Shortest series for which the feature is defined. [`compute_set`](crate::compute_set) and
+    /// the pipelines and classifiers built on it give NaN for shorter series, while
+    /// [`compute`](crate::compute) follows the reference implementation and may panic or give a
+    /// meaningless value.
+    pub min_length: usize,
+    /// Whether the value is a lag, count or run length in samples, whose range grows with the
+    /// length of the series.
+    pub length_dependent: bool,
+    /// Whether the value depends on the location or scale of the series, so that it is only
+    /// comparable between series after z-scoring, as catch22 does before computing it.
+    pub assumes_zscored: bool,
+    /// The hctsa operation the feature comes from, empty if it has none.
+    pub hctsa: &'static str,
+    /// Literature reference for the method, empty if it has none.
+    pub reference: &'static str,
+}
+
+impl FeatureInfo {
+    pub fn has_keyword(&self, keyword: &str) -> bool {
+        self.keywords.contains(&keyword)
+    }
+}
+
+const CATCH22: &str = "Lubba et al. (2019), catch22: CAnonical Time-series CHaracteristics. Data Mining and Knowledge Discovery 33, 1821-1852.";
+const HCTSA: &str = "Fulcher & Jones (2017), hctsa: A computational framework for automated time-series phenotyping using massive feature extraction. Cell Systems 5, 527-531.";
+
+const fn p(name: &'static str, value: &'static str) -> Parameter {
+    Parameter { name, value }
+}
+
+static INFO: [FeatureInfo; N_FEATURES] = [
+    FeatureInfo {
+        feature: Feature::OutlierTimingNeg,
+        name: FEATURE_NAMES[0],
+        short_name: "outlier_timing_neg",
+        description: "Timing of extreme negative events: the median position, relative to the middle of the series, of the values below increasingly negative thresholds.",
+        keywords: &["distribution", "outliers", "timing"],
+        parameters: &[
+            p("direction", "negative"),
+            p("increment", "0.01"),
+            p("trim_threshold", "2%"),
+        ],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "DN_OutlierInclude",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::OutlierTimingPos,
+        name: FEATURE_NAMES[1],
+        short_name: "outlier_timing_pos",
+        description: "Timing of extreme positive events: the median position, relative to the middle of the series, of the values above increasingly positive thresholds.",
+        keywords: &["distribution", "outliers", "timing"],
+        parameters: &[
+            p("direction", "positive"),
+            p("increment", "0.01"),
+            p("trim_threshold", "2%"),
+        ],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "DN_OutlierInclude",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::Mode5,
+        name: FEATURE_NAMES[2],
+        short_name: "mode_5",
+        description: "Mode of the distribution, as the centre of the fullest bin of a 5-bin histogram.",
+        keywords: &["distribution", "location", "histogram"],
+        parameters: &[p("n_bins", "5")],
+        min_length: 1,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "DN_HistogramMode",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::Mode10,
+        name: FEATURE_NAMES[3],
+        short_name: "mode_10",
+        description: "Mode of the distribution, as the centre of the fullest bin of a 10-bin histogram.",
+        keywords: &["distribution", "location", "histogram"],
+        parameters: &[p("n_bins", "10")],
+        min_length: 1,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "DN_HistogramMode",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::EmbeddingDist,
+        name: FEATURE_NAMES[4],
+        short_name: "embedding_dist",
+        description: "Mean deviation of the distribution of distances between successive points of a two-dimensional delay embedding from an exponential fit.",
+        keywords: &["correlation", "embedding", "distribution"],
+        parameters: &[
+            p("tau", "first zero of the ACF, at most n/10"),
+            p("bin_rule", "scott"),
+        ],
+        min_length: 3,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "CO_Embed2_Dist",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::AcfTimescale,
+        name: FEATURE_NAMES[5],
+        short_name: "acf_timescale",
+        description: "First lag at which the autocorrelation function falls below 1/e.",
+        keywords: &["correlation", "autocorrelation", "timescale"],
+        parameters: &[p("threshold", "1/e")],
+        min_length: 3,
+        length_dependent: true,
+        assumes_zscored: false,
+        hctsa: "CO_f1ecac",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::AcfFirstMin,
+        name: FEATURE_NAMES[6],
+        short_name: "acf_first_min",
+        description: "First minimum of the autocorrelation function.",
+        keywords: &["correlation", "autocorrelation", "timescale"],
+        parameters: &[],
+        min_length: 3,
+        length_dependent: true,
+        assumes_zscored: false,
+        hctsa: "CO_FirstMin",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::Ami2,
+        name: FEATURE_NAMES[7],
+        short_name: "ami2",
+        description: "Automutual information at lag 2, from a joint histogram with 5 equal-width bins per axis.",
+        keywords: &["correlation", "information", "histogram"],
+        parameters: &[p("tau", "2"), p("n_bins", "5")],
+        min_length: 3,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "CO_HistogramAMI",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::Trev,
+        name: FEATURE_NAMES[8],
+        short_name: "trev",
+        description: "Time-reversibility statistic: the mean of the cubed successive differences.",
+        keywords: &["correlation", "nonlinear", "time-reversal"],
+        parameters: &[p("tau", "1")],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "CO_trev",
+        reference: "Schreiber & Schmitz (2000), Surrogate time series. Physica D 142, 346-382.",
+    },
+    FeatureInfo {
+        feature: Feature::WhitenTimescale,
+        name: FEATURE_NAMES[9],
+        short_name: "whiten_timescale",
+        description: "Ratio of the first zero of the autocorrelation of the residuals of a forecast by the previous value to that of the series.",
+        keywords: &["forecasting", "autocorrelation", "timescale"],
+        parameters: &[p("train_length", "1")],
+        min_length: 3,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "FC_LocalSimple",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::ForecastError,
+        name: FEATURE_NAMES[10],
+        short_name: "forecast_error",
+        description: "Standard deviation of the residuals of a forecast by the mean of the previous three values.",
+        keywords: &["forecasting", "residuals"],
+        parameters: &[p("train_length", "3")],
+        min_length: 5,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "FC_LocalSimple",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::AmiTimescale,
+        name: FEATURE_NAMES[11],
+        short_name: "ami_timescale",
+        description: "First minimum of the automutual information function up to lag 40, with a Gaussian estimator.",
+        keywords: &["information", "correlation", "timescale"],
+        parameters: &[p("max_tau", "40"), p("estimator", "gaussian")],
+        min_length: 3,
+        length_dependent: true,
+        assumes_zscored: false,
+        hctsa: "IN_AutoMutualInfoStats",
+        reference: "Fraser & Swinney (1986), Independent coordinates for strange attractors from mutual information. Physical Review A 33, 1134-1140.",
+    },
+    FeatureInfo {
+        feature: Feature::HighFluctuation,
+        name: FEATURE_NAMES[12],
+        short_name: "high_fluctuation",
+        description: "Proportion of successive differences larger than 40 ms, i.e. 0.04 in the units of a z-scored series.",
+        keywords: &["medical", "differences"],
+        parameters: &[p("threshold", "40 ms")],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "MD_hrv_classic",
+        reference: "Mietus et al. (2002), The pNNx files: re-examining a widely used heart rate variability measure. Heart 88, 378-380.",
+    },
+    FeatureInfo {
+        feature: Feature::StretchDecreasing,
+        name: FEATURE_NAMES[13],
+        short_name: "stretch_decreasing",
+        description: "Longest run of successive decreases.",
+        keywords: &["symbolic", "stretch", "differences"],
+        parameters: &[],
+        min_length: 2,
+        length_dependent: true,
+        assumes_zscored: false,
+        hctsa: "SB_BinaryStats",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::StretchHigh,
+        name: FEATURE_NAMES[14],
+        short_name: "stretch_high",
+        description: "Longest run of consecutive values above the mean.",
+        keywords: &["symbolic", "stretch", "distribution"],
+        parameters: &[],
+        min_length: 2,
+        length_dependent: true,
+        assumes_zscored: false,
+        hctsa: "SB_BinaryStats",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::EntropyPairs,
+        name: FEATURE_NAMES[15],
+        short_name: "entropy_pairs",
+        description: "Entropy of the pairs of successive symbols of a 3-letter equiprobable coarse-graining.",
+        keywords: &["symbolic", "entropy", "motifs"],
+        parameters: &[p("alphabet_size", "3"), p("word_length", "2")],
+        min_length: 3,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "SB_MotifThree",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::RsRange,
+        name: FEATURE_NAMES[16],
+        short_name: "rs_range",
+        description: "Proportion of the timescales before the breakpoint of a two-segment fit to the rescaled-range fluctuation function.",
+        keywords: &["fluctuation", "scaling"],
+        parameters: &[
+            p("lag", "1"),
+            p("min_tau", "5"),
+            p("n_steps", "50"),
+            p("min_points", "6"),
+        ],
+        min_length: 32,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "SC_FluctAnal",
+        reference: "Hurst (1951), Long-term storage capacity of reservoirs. Transactions of the American Society of Civil Engineers 116, 770-799.",
+    },
+    FeatureInfo {
+        feature: Feature::Dfa,
+        name: FEATURE_NAMES[17],
+        short_name: "dfa",
+        description: "Proportion of the timescales before the breakpoint of a two-segment fit to the detrended fluctuation function.",
+        keywords: &["fluctuation", "scaling"],
+        parameters: &[
+            p("lag", "2"),
+            p("min_tau", "5"),
+            p("n_steps", "50"),
+            p("min_points", "6"),
+        ],
+        min_length: 32,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "SC_FluctAnal",
+        reference: "Peng et al. (1994), Mosaic organization of DNA nucleotides. Physical Review E 49, 1685-1689.",
+    },
+    FeatureInfo {
+        feature: Feature::LowFreqPower,
+        name: FEATURE_NAMES[18],
+        short_name: "low_freq_power",
+        description: "Power in the lowest fifth of the frequencies of the Welch power spectrum.",
+        keywords: &["spectral", "power"],
+        parameters: &[p("window", "rectangular"), p("fraction", "1/5")],
+        min_length: 5,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "SP_Summaries",
+        reference: "Welch (1967), The use of fast Fourier transform for the estimation of power spectra. IEEE Transactions on Audio and Electroacoustics 15, 70-73.",
+    },
+    FeatureInfo {
+        feature: Feature::CentroidFreq,
+        name: FEATURE_NAMES[19],
+        short_name: "centroid_freq",
+        description: "Frequency below which half the power of the Welch power spectrum lies.",
+        keywords: &["spectral", "centroid"],
+        parameters: &[p("window", "rectangular")],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "SP_Summaries",
+        reference: "Welch (1967), The use of fast Fourier transform for the estimation of power spectra. IEEE Transactions on Audio and Electroacoustics 15, 70-73.",
+    },
+    FeatureInfo {
+        feature: Feature::TransitionMatrix,
+        name: FEATURE_NAMES[20],
+        short_name: "transition_matrix",
+        description: "Trace of the covariance of the transition matrix between the three symbols of the series coarse-grained after downsampling at the first zero of the ACF.",
+        keywords: &["symbolic", "transition-matrix", "correlation"],
+        parameters: &[p("n_groups", "3"), p("tau", "first zero of the ACF")],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "SB_TransitionMatrix",
+        reference: CATCH22,
+    },
+    FeatureInfo {
+        feature: Feature::Periodicity,
+        name: FEATURE_NAMES[21],
+        short_name: "periodicity",
+        description: "Wang's periodicity: the lag of the first peak of the autocovariance of the spline-detrended series above a threshold.",
+        keywords: &["periodicity", "autocorrelation"],
+        parameters: &[p("threshold", "0.01")],
+        min_length: 7,
+        length_dependent: true,
+        assumes_zscored: true,
+        hctsa: "PD_PeriodicityWang",
+        reference: "Wang, Smith & Hyndman (2006), Characteristic-based clustering for time series data. Data Mining and Knowledge Discovery 13, 335-364.",
+    },
+    FeatureInfo {
+        feature: Feature::Mean,
+        name: FEATURE_NAMES[22],
+        short_name: "mean",
+        description: "Mean of the series.",
+        keywords: &["distribution", "location"],
+        parameters: &[],
+        min_length: 1,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "DN_Mean",
+        reference: HCTSA,
+    },
+    FeatureInfo {
+        feature: Feature::Sd,
+        name: FEATURE_NAMES[23],
+        short_name: "SD",
+        description: "Sample standard deviation of the series.",
+        keywords: &["distribution", "spread"],
+        parameters: &[],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: false,
+        hctsa: "DN_Spread",
+        reference: HCTSA,
+    },
+    FeatureInfo {
+        feature: Feature::Slope,
+        name: FEATURE_NAMES[24],
+        short_name: "slope",
+        description: "Slope of the least-squares line through the series against time.",
+        keywords: &["trend"],
+        parameters: &[],
+        min_length: 2,
+        length_dependent: false,
+        assumes_zscored: true,
+        hctsa: "",
+        reference: "",
+    },
+];
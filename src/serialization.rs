@@ -0,0 +1,172 @@
+//! Serde support behind the `serde` feature.
+//!
+//! [`Features`] are stored as `{"version": 1, "features": {"DN_Mean": 0.1, ...}}`, keyed by the
+//! canonical names in order. NaN values are written as `null`, which is how JSON stores them
+//! anyway, and read back as NaN. Loading rejects unknown and repeated names and reads short
+//! names as the canonical ones. [`Feature`] is its long name and [`FeatureSet`] a list of them.
+
+use alloc::string::String;
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, SerializeStruct, Serializer};
+
+use crate::{Feature, FeatureSet, Features};
+
+impl Serialize for Features {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Features", 2)?;
+        state.serialize_field("version", &Features::FORMAT_VERSION)?;
+        state.serialize_field("features", &Values(self))?;
+        state.end()
+    }
+}
+
+/// The name-value pairs of a [`Features`], as a map.
+struct Values<'a>(&'a Features);
+
+impl Serialize for Values<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in self.0.iter() {
+            map.serialize_entry(name, &(!value.is_nan()).then_some(value))?;
+        }
+        map.end()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct Stored {
+    version: u32,
+    #[serde(deserialize_with = "values")]
+    features: Features,
+}
+
+fn values<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Features, D::Error> {
+    struct ValuesVisitor;
+
+    impl<'de> Visitor<'de> for ValuesVisitor {
+        type Value = Features;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map of feature names to values")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Features, A::Error> {
+            let mut features = Features::default();
+            while let Some((name, value)) = map.next_entry::<String, Option<f64>>()? {
+                let name = match Feature::from_name(&name) {
+                    Some(feature) => String::from(feature.name()),
+                    None if is_entropy_name(&name) => name,
+                    None => {
+                        return Err(de::Error::custom(format_args!("unknown feature: {name}")));
+                    }
+                };
+                if features.get(&name).is_some() {
+                    return Err(de::Error::custom(format_args!("repeated feature: {name}")));
+                }
+                features.push(name, value.unwrap_or(f64::NAN));
+            }
+            Ok(features)
+        }
+    }
+
+    deserializer.deserialize_map(ValuesVisitor)
+}
+
+/// Whether `name` is one of the entropy features that [`compute_all`](crate::compute_all) appends.
+fn is_entropy_name(name: &str) -> bool {
+    let number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let two_numbers = |s: &str, sep: &str| {
+        s.split_once(sep)
+            .is_some_and(|(a, b)| number(a) && number(b))
+    };
+
+    if let Some(m) = name
+        .strip_prefix("EN_SampEn_m")
+        .or_else(|| name.strip_prefix("EN_ApEn_m"))
+    {
+        number(m)
+    } else if let Some(rest) = name.strip_prefix("EN_PermEn_d") {
+        two_numbers(rest, "_tau")
+    } else if let Some(rest) = name.strip_prefix("EN_MSE_m") {
+        two_numbers(rest, "_scale")
+    } else {
+        false
+    }
+}
+
+impl<'de> Deserialize<'de> for Features {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = Stored::deserialize(deserializer)?;
+        if stored.version != Features::FORMAT_VERSION {
+            return Err(de::Error::custom(format_args!(
+                "unsupported features format version {}, expected {}",
+                stored.version,
+                Features::FORMAT_VERSION
+            )));
+        }
+        Ok(stored.features)
+    }
+}
+
+impl Serialize for Feature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Feature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FeatureVisitor;
+
+        impl Visitor<'_> for FeatureVisitor {
+            type Value = Feature;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a feature name")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Feature, E> {
+                Feature::from_name(name)
+                    .ok_or_else(|| E::custom(format_args!("unknown feature: {name}")))
+            }
+        }
+
+        deserializer.deserialize_str(FeatureVisitor)
+    }
+}
+
+impl Serialize for FeatureSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for feature in self.iter() {
+            seq.serialize_element(&feature)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for FeatureSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SetVisitor;
+
+        impl<'de> Visitor<'de> for SetVisitor {
+            type Value = FeatureSet;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of feature names")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<FeatureSet, A::Error> {
+                let mut set = FeatureSet::empty();
+                while let Some(feature) = seq.next_element::<Feature>()? {
+                    set.insert(feature);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(SetVisitor)
+    }
+}
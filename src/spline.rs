@@ -0,0 +1,260 @@
+//! Least-squares B-spline fits of a series against its sample index, as used to detrend the
+//! series in `PD_PeriodicityWang_th0_01`.
+//!
+//! Splines are stored on a clamped knot vector: the first and last breaks are repeated
+//! `degree + 1` times and the interior breaks appear once, so the fit is `degree - 1` times
+//! continuously differentiable at each interior break.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+use crate::linalg::BandedQr;
+use crate::statistics::linspace;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BSpline {
+    pub degree: usize,
+    pub knots: Vec<f64>,
+    /// Coefficient of each B-spline basis function, `knots.len() - degree - 1` in all.
+    pub coefs: Vec<f64>,
+}
+
+/// Values of the `degree + 1` basis functions that are non-zero at `t` in knot span `span`
+/// (Cox–de Boor recursion), left at the start of `work`, which holds `3 * (degree + 1)` values.
+fn basis_functions(knots: &[f64], degree: usize, span: usize, t: f64, work: &mut [f64]) {
+    let (n, rest) = work.split_at_mut(degree + 1);
+    let (left, right) = rest.split_at_mut(degree + 1);
+    n[0] = 1.0;
+
+    for j in 1..=degree {
+        left[j] = t - knots[span + 1 - j];
+        right[j] = knots[span + j] - t;
+        let mut saved = 0.0;
+        for r in 0..j {
+            let temp = n[r] / (right[r + 1] + left[j - r]);
+            n[r] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        n[j] = saved;
+    }
+}
+
+fn eval_at(knots: &[f64], coefs: &[f64], degree: usize, t: f64, work: &mut [f64]) -> f64 {
+    let span = find_span(knots, degree, coefs.len(), t);
+    basis_functions(knots, degree, span, t, work);
+    work[..=degree]
+        .iter()
+        .enumerate()
+        .map(|(r, b)| b * coefs[span - degree + r])
+        .sum()
+}
+
+/// Index of the knot span containing `t`. Values outside the breaks fall in the first or last
+/// span, so the spline is extrapolated by its end polynomials.
+fn find_span(knots: &[f64], degree: usize, n_coefs: usize, t: f64) -> usize {
+    let span = knots.partition_point(|&k| k <= t).saturating_sub(1);
+    span.clamp(degree, n_coefs - 1)
+}
+
+fn clamped_knots_into(breaks: &[f64], degree: usize, knots: &mut Vec<f64>) {
+    knots.clear();
+    knots.extend(core::iter::repeat_n(breaks[0], degree));
+    knots.extend_from_slice(breaks);
+    knots.extend(core::iter::repeat_n(breaks[breaks.len() - 1], degree));
+}
+
+/// Reusable buffers for fitting a spline to `breaks` and evaluating it.
+#[derive(Debug, Default)]
+pub(crate) struct SplineScratch {
+    pub(crate) breaks: Vec<f64>,
+    degree: usize,
+    knots: Vec<f64>,
+    coefs: Vec<f64>,
+    qr: BandedQr,
+    basis: Vec<f64>,
+}
+
+impl SplineScratch {
+    pub(crate) fn with_capacity(n_breaks: usize, degree: usize) -> Self {
+        let n_coefs = n_breaks + degree - 1;
+        SplineScratch {
+            breaks: Vec::with_capacity(n_breaks),
+            degree,
+            knots: Vec::with_capacity(n_breaks + 2 * degree),
+            coefs: Vec::with_capacity(n_coefs),
+            qr: BandedQr::with_capacity(n_coefs, degree + 1),
+            basis: Vec::with_capacity(3 * (degree + 1)),
+        }
+    }
+
+    /// Least-squares spline of `degree` on `self.breaks`, as [`BSpline::fit_with_breaks`].
+    pub(crate) fn fit(&mut self, a: &[f64], degree: usize) -> Result<(), Error> {
+        let breaks = &self.breaks;
+        if breaks.len() < 2 {
+            return Err(Error::InvalidParameter("at least 2 breaks are required"));
+        }
+        if breaks.iter().any(|b| !b.is_finite()) || breaks.windows(2).any(|w| w[0] >= w[1]) {
+            return Err(Error::InvalidParameter(
+                "breaks must be finite and strictly increasing",
+            ));
+        }
+
+        self.degree = degree;
+        let knots = &mut self.knots;
+        clamped_knots_into(breaks, degree, knots);
+        let n_coefs = knots.len() - degree - 1;
+        if a.len() < n_coefs {
+            return Err(Error::TooShort {
+                len: a.len(),
+                min: n_coefs,
+            });
+        }
+
+        // QR of the design matrix, whose row for each sample holds the degree + 1 basis
+        // functions that are non-zero there
+        self.qr.reset(n_coefs, degree + 1);
+        self.basis.clear();
+        self.basis.resize(3 * (degree + 1), 0.0);
+        for i in 0..a.len() {
+            let t = i as f64;
+            let span = find_span(knots, degree, n_coefs, t);
+            basis_functions(knots, degree, span, t, &mut self.basis);
+            self.qr.add_row(span - degree, &self.basis[..=degree], a[i]);
+        }
+
+        self.qr.solve_into(&mut self.coefs)
+    }
+
+    /// The fitted spline at `t`.
+    pub(crate) fn eval(&mut self, t: f64) -> f64 {
+        eval_at(&self.knots, &self.coefs, self.degree, t, &mut self.basis)
+    }
+}
+
+impl BSpline {
+    /// Least-squares spline of `degree` with `n_breaks` evenly spaced breaks, the first at 0 and
+    /// the last at `a.len() - 1`.
+    pub fn fit(a: &[f64], degree: usize, n_breaks: usize) -> Result<BSpline, Error> {
+        if n_breaks < 2 {
+            return Err(Error::InvalidParameter("n_breaks must be at least 2"));
+        }
+        // two samples to span the breaks, and one per coefficient
+        let min = (n_breaks + degree - 1).max(2);
+        if a.len() < min {
+            return Err(Error::TooShort { len: a.len(), min });
+        }
+        let breaks = linspace(0.0, (a.len() - 1) as f64, n_breaks);
+        BSpline::fit_with_breaks(a, degree, &breaks)
+    }
+
+    /// Least-squares spline of `degree` with the given strictly increasing breaks, in units of
+    /// samples. Samples outside the breaks are fitted by the end polynomials.
+    pub fn fit_with_breaks(a: &[f64], degree: usize, breaks: &[f64]) -> Result<BSpline, Error> {
+        let mut scratch = SplineScratch::default();
+        scratch.breaks.extend_from_slice(breaks);
+        scratch.fit(a, degree)?;
+
+        Ok(BSpline {
+            degree,
+            knots: scratch.knots,
+            coefs: scratch.coefs,
+        })
+    }
+
+    /// Distinct knots, i.e. the breaks of the fit.
+    pub fn breaks(&self) -> Vec<f64> {
+        let mut breaks = self.knots[self.degree..self.knots.len() - self.degree].to_vec();
+        breaks.dedup();
+        breaks
+    }
+
+    pub fn eval(&self, t: f64) -> f64 {
+        let mut work = vec![0.0; 3 * (self.degree + 1)];
+        eval_at(&self.knots, &self.coefs, self.degree, t, &mut work)
+    }
+
+    /// The spline at the sample positions `0..len`.
+    pub fn values(&self, len: usize) -> Vec<f64> {
+        (0..len).map(|i| self.eval(i as f64)).collect()
+    }
+
+    /// First derivative, a spline of one degree less on the same breaks.
+    pub fn derivative(&self) -> BSpline {
+        let p = self.degree;
+        if p == 0 {
+            return BSpline {
+                degree: 0,
+                knots: self.knots.clone(),
+                coefs: vec![0.0; self.coefs.len()],
+            };
+        }
+
+        let knots = self.knots[1..self.knots.len() - 1].to_vec();
+        let coefs = (0..self.coefs.len() - 1)
+            .map(|i| {
+                let h = self.knots[i + p + 1] - self.knots[i + 1];
+                if h > 0.0 {
+                    p as f64 * (self.coefs[i + 1] - self.coefs[i]) / h
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        BSpline {
+            degree: p - 1,
+            knots,
+            coefs,
+        }
+    }
+
+    /// Derivative of the given order at `t`.
+    pub fn eval_derivative(&self, t: f64, order: usize) -> f64 {
+        if order > self.degree {
+            return 0.0;
+        }
+        let mut d = self.clone();
+        for _ in 0..order {
+            d = d.derivative();
+        }
+        d.eval(t)
+    }
+
+    /// The series minus the spline, i.e. the detrended series.
+    pub fn residuals(&self, a: &[f64]) -> Vec<f64> {
+        let mut work = vec![0.0; 3 * (self.degree + 1)];
+        a.iter()
+            .enumerate()
+            .map(|(i, x)| x - eval_at(&self.knots, &self.coefs, self.degree, i as f64, &mut work))
+            .collect()
+    }
+}
+
+/// The detrending spline of `PD_PeriodicityWang_th0_01`: cubic, two pieces, breaking at sample
+/// `floor(n / 2) - 1`.
+pub fn splinefit(a: &[f64]) -> Result<BSpline, Error> {
+    let mut scratch = SplineScratch::default();
+    splinefit_with(a, &mut scratch)?;
+
+    Ok(BSpline {
+        degree: 3,
+        knots: scratch.knots,
+        coefs: scratch.coefs,
+    })
+}
+
+/// [`splinefit`] in reusable buffers.
+pub(crate) fn splinefit_with(a: &[f64], scratch: &mut SplineScratch) -> Result<(), Error> {
+    if a.len() < 5 {
+        return Err(Error::TooShort {
+            len: a.len(),
+            min: 5,
+        });
+    }
+    scratch.breaks.clear();
+    scratch
+        .breaks
+        .extend([0.0, (a.len() / 2 - 1) as f64, (a.len() - 1) as f64]);
+    scratch.fit(a, 3)
+}
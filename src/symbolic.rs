@@ -0,0 +1,331 @@
+//! Symbolic representations of a series: coarse-graining into an alphabet and the statistics of
+//! the words formed by consecutive symbols.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::statistics::{
+    coarsegrain_into, coarsegrain_sorted, f_entropy, max_, mean, min_, std_dev,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Method {
+    /// Bins holding equal numbers of values, bounded by the sample quantiles.
+    #[default]
+    Quantile,
+    /// Bins of equal width between the minimum and maximum.
+    EqualWidth,
+    /// SAX: bins of equal probability under a standard normal, applied to the z-scored series.
+    Sax,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymbolicConfig {
+    /// At least 1.
+    pub alphabet_size: usize,
+    pub method: Method,
+    /// Word lengths `1..=max_word_len` are analysed; at least 1.
+    pub max_word_len: usize,
+}
+
+impl Default for SymbolicConfig {
+    /// The settings of `SB_MotifThree_quantile`.
+    fn default() -> Self {
+        SymbolicConfig {
+            alphabet_size: 3,
+            method: Method::Quantile,
+            max_word_len: 2,
+        }
+    }
+}
+
+/// Distribution of the words of one length. Words are indexed by their code: the symbols read
+/// as the digits of a base-`alphabet_size` number, first symbol most significant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordStats {
+    pub length: usize,
+    pub probabilities: Vec<f64>,
+    /// Shannon entropy of the word distribution in nats.
+    pub entropy: f64,
+    /// Entropy of the last symbol given the preceding `length - 1`, estimated as the difference
+    /// of the block entropies. Equal to `entropy` for single symbols.
+    pub conditional_entropy: f64,
+    pub most_frequent: Vec<usize>,
+    /// Least frequent of the words that occur.
+    pub least_frequent: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbolic {
+    /// Symbol of each value, in `0..alphabet_size`.
+    pub symbols: Vec<usize>,
+    pub alphabet_size: usize,
+    /// Statistics for word lengths `1..=max_word_len`.
+    pub words: Vec<WordStats>,
+}
+
+/// Inverse of the standard normal CDF (Acklam's rational approximation, relative error below
+/// 1.2e-9).
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.38357751867269e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    let p_low = 0.02425;
+
+    if p < p_low {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - p_low {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+/// Maps every value to a symbol in `0..alphabet_size`.
+pub fn coarse_grain(a: &[f64], alphabet_size: usize, method: Method) -> Vec<usize> {
+    let mut symbols = Vec::new();
+    coarse_grain_into(
+        a,
+        alphabet_size,
+        method,
+        None,
+        &mut Vec::new(),
+        &mut symbols,
+    );
+    symbols
+}
+
+/// [`coarse_grain`] into `symbols`, with `work` holding the sorted values or the breakpoints.
+/// The quantile method uses `sorted`, the values of `a` in ascending order, when given.
+pub(crate) fn coarse_grain_into(
+    a: &[f64],
+    alphabet_size: usize,
+    method: Method,
+    sorted: Option<&[f64]>,
+    work: &mut Vec<f64>,
+    symbols: &mut Vec<usize>,
+) {
+    symbols.clear();
+    if a.is_empty() || alphabet_size == 0 {
+        return;
+    }
+
+    match method {
+        Method::Quantile => {
+            match sorted {
+                Some(sorted) => coarsegrain_sorted(a, alphabet_size, sorted, symbols),
+                None => coarsegrain_into(a, alphabet_size, work, symbols),
+            }
+            symbols.iter_mut().for_each(|s| *s = s.saturating_sub(1));
+        }
+        Method::EqualWidth => {
+            let min_val = min_(a);
+            let width = (max_(a) - min_val) / alphabet_size as f64;
+            if width == 0.0 {
+                symbols.resize(a.len(), 0);
+                return;
+            }
+            symbols.extend(
+                a.iter()
+                    .map(|x| (((x - min_val) / width) as usize).min(alphabet_size - 1)),
+            );
+        }
+        Method::Sax => {
+            let m = mean(a);
+            let sd = if a.len() > 1 { std_dev(a) } else { 0.0 };
+            work.clear();
+            work.extend(
+                (1..alphabet_size).map(|k| normal_quantile(k as f64 / alphabet_size as f64)),
+            );
+            symbols.extend(a.iter().map(|x| {
+                let z = if sd > 0.0 { (x - m) / sd } else { 0.0 };
+                work.partition_point(|&b| b < z)
+            }));
+        }
+    }
+}
+
+/// Frequencies of the words of `length` consecutive symbols, indexed by word code.
+pub fn word_counts(symbols: &[usize], alphabet_size: usize, length: usize) -> Vec<usize> {
+    let mut counts = Vec::new();
+    word_counts_into(symbols, alphabet_size, length, &mut counts);
+    counts
+}
+
+fn word_counts_into(
+    symbols: &[usize],
+    alphabet_size: usize,
+    length: usize,
+    counts: &mut Vec<usize>,
+) {
+    counts.clear();
+    counts.resize(alphabet_size.pow(length as u32), 0);
+    if length == 0 || symbols.len() < length {
+        return;
+    }
+
+    for w in symbols.windows(length) {
+        let code = w.iter().fold(0, |acc, &s| acc * alphabet_size + s);
+        counts[code] += 1;
+    }
+}
+
+/// Shannon entropy of the word frequencies, leaving the probabilities in `probabilities`.
+fn block_entropy(counts: &[usize], n_words: usize, probabilities: &mut Vec<f64>) -> f64 {
+    probabilities.clear();
+    probabilities.extend(counts.iter().map(|&c| c as f64 / n_words as f64));
+    f_entropy(probabilities)
+}
+
+/// Symbols of the word with the given code.
+pub fn decode_word(code: usize, alphabet_size: usize, length: usize) -> Vec<usize> {
+    let mut word = vec![0; length];
+    let mut code = code;
+    for i in (0..length).rev() {
+        word[i] = code % alphabet_size;
+        code /= alphabet_size;
+    }
+    word
+}
+
+fn word_stats(symbols: &[usize], alphabet_size: usize, length: usize, prev: f64) -> WordStats {
+    let counts = word_counts(symbols, alphabet_size, length);
+    let n_words = (symbols.len() + 1).saturating_sub(length);
+
+    let mut probabilities = Vec::new();
+    let entropy = block_entropy(&counts, n_words, &mut probabilities);
+
+    let mut most = 0;
+    let mut least = None;
+    for (code, &c) in counts.iter().enumerate() {
+        if c > counts[most] {
+            most = code;
+        }
+        if c > 0 && least.is_none_or(|l: usize| c < counts[l]) {
+            least = Some(code);
+        }
+    }
+
+    WordStats {
+        length,
+        probabilities,
+        entropy,
+        conditional_entropy: entropy - prev,
+        most_frequent: decode_word(most, alphabet_size, length),
+        least_frequent: least
+            .map(|l| decode_word(l, alphabet_size, length))
+            .unwrap_or_default(),
+    }
+}
+
+/// Coarse-grains `a` and computes the statistics of its words. Fails if the alphabet or the word
+/// length is 0, or if there are more possible words than fit in a `usize`.
+pub fn symbolic(a: &[f64], config: &SymbolicConfig) -> Result<Symbolic, Error> {
+    if config.alphabet_size == 0 {
+        return Err(Error::InvalidParameter("alphabet_size must be at least 1"));
+    }
+    if config.max_word_len == 0 {
+        return Err(Error::InvalidParameter("max_word_len must be at least 1"));
+    }
+    if u32::try_from(config.max_word_len)
+        .ok()
+        .and_then(|len| config.alphabet_size.checked_pow(len))
+        .is_none()
+    {
+        return Err(Error::InvalidParameter("too many possible words"));
+    }
+
+    let symbols = coarse_grain(a, config.alphabet_size, config.method);
+
+    let mut words = Vec::with_capacity(config.max_word_len);
+    let mut prev = 0.0;
+    for length in 1..=config.max_word_len {
+        let stats = word_stats(&symbols, config.alphabet_size, length, prev);
+        prev = stats.entropy;
+        words.push(stats);
+    }
+
+    Ok(Symbolic {
+        symbols,
+        alphabet_size: config.alphabet_size,
+        words,
+    })
+}
+
+/// Reusable buffers for [`word_entropy_with`].
+#[derive(Debug, Default)]
+pub(crate) struct SymbolicScratch {
+    work: Vec<f64>,
+    symbols: Vec<usize>,
+    counts: Vec<usize>,
+    probabilities: Vec<f64>,
+}
+
+impl SymbolicScratch {
+    pub(crate) fn with_capacity(len: usize, config: &SymbolicConfig) -> Self {
+        let n_words = config.alphabet_size.pow(config.max_word_len as u32);
+        SymbolicScratch {
+            work: Vec::with_capacity(len.max(config.alphabet_size)),
+            symbols: Vec::with_capacity(len),
+            counts: Vec::with_capacity(n_words),
+            probabilities: Vec::with_capacity(n_words),
+        }
+    }
+}
+
+/// Entropy of the words of `config.max_word_len` symbols, as in [`symbolic`], without
+/// allocating once the buffers have grown. `sorted` optionally holds the values of `a` in
+/// ascending order.
+pub(crate) fn word_entropy_with(
+    a: &[f64],
+    config: &SymbolicConfig,
+    sorted: Option<&[f64]>,
+    scratch: &mut SymbolicScratch,
+) -> f64 {
+    let (alphabet_size, length) = (config.alphabet_size, config.max_word_len);
+    coarse_grain_into(
+        a,
+        alphabet_size,
+        config.method,
+        sorted,
+        &mut scratch.work,
+        &mut scratch.symbols,
+    );
+    word_counts_into(&scratch.symbols, alphabet_size, length, &mut scratch.counts);
+    let n_words = (scratch.symbols.len() + 1).saturating_sub(length);
+    block_entropy(&scratch.counts, n_words, &mut scratch.probabilities)
+}
@@ -0,0 +1,306 @@
+//! Markov transition structure of a coarse-grained, downsampled series, as used by
+//! `SB_TransitionMatrix_3ac_sumdiagcov`.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Error;
+use crate::fft::FftBuffer;
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::Float;
+use crate::statistics::{covariance_trace, first_zero_with, is_constant};
+use crate::symbolic::{self, coarse_grain_into};
+
+/// Downsampling step applied before coarse-graining.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tau {
+    /// First zero crossing of the autocorrelation function.
+    #[default]
+    FirstZero,
+    Fixed(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionConfig {
+    pub groups: usize,
+    pub tau: Tau,
+    pub coarse_grain_method: symbolic::Method,
+}
+
+impl Default for TransitionConfig {
+    /// The settings of `SB_TransitionMatrix_3ac`.
+    fn default() -> Self {
+        TransitionConfig {
+            groups: 3,
+            tau: Tau::FirstZero,
+            coarse_grain_method: symbolic::Method::Quantile,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionMatrix {
+    /// Downsampling step that was used.
+    pub tau: usize,
+    /// Joint probabilities of consecutive symbols: `matrix[i][j]` is the proportion of
+    /// transitions from group `i` to group `j`.
+    pub matrix: Vec<Vec<f64>>,
+    /// Entropy rate of the fitted Markov chain in nats.
+    pub entropy_rate: f64,
+    /// Stationary distribution of the transition probabilities.
+    pub stationary: Vec<f64>,
+    /// Probability of staying in the same group (trace of `matrix`).
+    pub diagonal_mass: f64,
+    /// One minus the modulus of the second largest eigenvalue of the transition probabilities.
+    pub spectral_gap: f64,
+    /// Trace of the covariance of the columns of `matrix`.
+    pub sum_diag_cov: f64,
+}
+
+impl TransitionMatrix {
+    /// Row-stochastic transition probabilities. Groups that are never left are treated as
+    /// absorbing.
+    pub fn probabilities(&self) -> Vec<Vec<f64>> {
+        self.matrix
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let total = row.iter().sum::<f64>();
+                if total > 0.0 {
+                    row.iter().map(|p| p / total).collect()
+                } else {
+                    (0..row.len())
+                        .map(|j| if i == j { 1.0 } else { 0.0 })
+                        .collect()
+                }
+            })
+            .collect()
+    }
+}
+
+fn mat_mul(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = a.len();
+    let mut c = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for k in 0..n {
+            for j in 0..n {
+                c[i][j] += a[i][k] * b[k][j];
+            }
+        }
+    }
+    c
+}
+
+fn max_norm(a: &[Vec<f64>]) -> f64 {
+    a.iter()
+        .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+/// Left eigenvector of `p` for eigenvalue 1 by power iteration from the uniform distribution.
+fn stationary_distribution(p: &[Vec<f64>]) -> Vec<f64> {
+    let n = p.len();
+    let mut pi = vec![1.0 / n as f64; n];
+
+    for _ in 0..10000 {
+        let mut next = vec![0.0; n];
+        for i in 0..n {
+            for j in 0..n {
+                next[j] += pi[i] * p[i][j];
+            }
+        }
+        // averaging with the previous iterate makes periodic chains converge
+        for j in 0..n {
+            next[j] = 0.5 * (next[j] + pi[j]);
+        }
+        let change = next
+            .iter()
+            .zip(&pi)
+            .map(|(a, b)| (a - b).abs())
+            .sum::<f64>();
+        pi = next;
+        if change < 1e-15 {
+            break;
+        }
+    }
+    pi
+}
+
+/// Spectral radius of `p - 1 πᵀ`, i.e. the second largest eigenvalue modulus of `p`, from
+/// Gelfand's formula `ρ(A) = lim ‖A^k‖^(1/k)` evaluated by repeated squaring.
+fn second_eigenvalue_modulus(p: &[Vec<f64>], pi: &[f64]) -> f64 {
+    let n = p.len();
+    let mut a = (0..n)
+        .map(|i| (0..n).map(|j| p[i][j] - pi[j]).collect::<Vec<f64>>())
+        .collect::<Vec<_>>();
+
+    let mut log_scale = 0.0;
+    let mut power = 1.0;
+    for _ in 0..60 {
+        a = mat_mul(&a, &a);
+        power *= 2.0;
+        log_scale *= 2.0;
+
+        let norm = max_norm(&a);
+        if norm == 0.0 {
+            return 0.0;
+        }
+        log_scale += norm.ln();
+        a.iter_mut().flatten().for_each(|x| *x /= norm);
+    }
+
+    (log_scale / power).exp()
+}
+
+/// Reusable buffers for the transition matrix.
+#[derive(Debug, Default)]
+pub(crate) struct TransitionScratch {
+    y_down: Vec<f64>,
+    work: Vec<f64>,
+    symbols: Vec<usize>,
+    /// Joint probabilities, row-major.
+    matrix: Vec<f64>,
+}
+
+impl TransitionScratch {
+    pub(crate) fn with_capacity(len: usize, config: &TransitionConfig) -> Self {
+        TransitionScratch {
+            y_down: Vec::with_capacity(len),
+            work: Vec::with_capacity(len.max(config.groups)),
+            symbols: Vec::with_capacity(len),
+            matrix: Vec::with_capacity(config.groups * config.groups),
+        }
+    }
+}
+
+/// Fills `scratch.matrix` with the joint probabilities of consecutive symbols and returns the
+/// downsampling step.
+fn joint_probabilities(
+    a: &[f64],
+    config: &TransitionConfig,
+    fft: &mut FftBuffer,
+    acf: &mut Vec<f64>,
+    scratch: &mut TransitionScratch,
+) -> Result<usize, Error> {
+    if config.groups == 0 {
+        return Err(Error::InvalidParameter("groups must be at least 1"));
+    }
+    if a.len() < 2 {
+        return Err(Error::TooShort {
+            len: a.len(),
+            min: 2,
+        });
+    }
+    if is_constant(a) {
+        return Err(Error::ConstantSeries);
+    }
+
+    let tau = match config.tau {
+        Tau::FirstZero => first_zero_with(a, a.len(), fft, acf),
+        Tau::Fixed(tau) => tau,
+    };
+    if tau == 0 {
+        return Err(Error::InvalidParameter("tau must be at least 1"));
+    }
+
+    let n_down = (a.len() - 1) / tau + 1;
+    if n_down < 2 {
+        return Err(Error::TooShort {
+            len: a.len(),
+            min: tau + 1,
+        });
+    }
+
+    let y_down = &mut scratch.y_down;
+    y_down.clear();
+    y_down.extend((0..n_down).map(|i| a[i * tau]));
+
+    let num_groups = config.groups;
+    coarse_grain_into(
+        y_down,
+        num_groups,
+        config.coarse_grain_method,
+        None,
+        &mut scratch.work,
+        &mut scratch.symbols,
+    );
+    let y_cg = &scratch.symbols;
+
+    let t = &mut scratch.matrix;
+    t.clear();
+    t.resize(num_groups * num_groups, 0.0);
+
+    for i in 0..n_down - 1 {
+        t[y_cg[i] * num_groups + y_cg[i + 1]] += 1.0;
+    }
+
+    for x in t.iter_mut() {
+        *x /= (n_down - 1) as f64;
+    }
+
+    Ok(tau)
+}
+
+pub fn transition_matrix(a: &[f64], config: &TransitionConfig) -> Result<TransitionMatrix, Error> {
+    let mut scratch = TransitionScratch::default();
+    let tau = joint_probabilities(
+        a,
+        config,
+        &mut FftBuffer::default(),
+        &mut Vec::new(),
+        &mut scratch,
+    )?;
+
+    let num_groups = config.groups;
+    let t = scratch
+        .matrix
+        .chunks(num_groups)
+        .map(<[f64]>::to_vec)
+        .collect::<Vec<_>>();
+
+    let mut diagonal_mass = 0.0;
+    let mut entropy_rate = 0.0;
+    for i in 0..num_groups {
+        diagonal_mass += t[i][i];
+        let p_i = t[i].iter().sum::<f64>();
+        for j in 0..num_groups {
+            if t[i][j] > 0.0 {
+                entropy_rate -= t[i][j] * (t[i][j] / p_i).ln();
+            }
+        }
+    }
+
+    let sum_diag_cov = covariance_trace(&scratch.matrix, num_groups, num_groups);
+
+    let mut out = TransitionMatrix {
+        tau,
+        matrix: t,
+        entropy_rate,
+        stationary: Vec::new(),
+        diagonal_mass,
+        spectral_gap: 0.0,
+        sum_diag_cov,
+    };
+
+    let p = out.probabilities();
+    out.stationary = stationary_distribution(&p);
+    out.spectral_gap = 1.0 - second_eigenvalue_modulus(&p, &out.stationary);
+
+    Ok(out)
+}
+
+/// The trace of the column covariance of [`transition_matrix`] in reusable buffers.
+pub(crate) fn sum_diag_cov_with(
+    a: &[f64],
+    config: &TransitionConfig,
+    fft: &mut FftBuffer,
+    acf: &mut Vec<f64>,
+    scratch: &mut TransitionScratch,
+) -> Result<f64, Error> {
+    joint_probabilities(a, config, fft, acf, scratch)?;
+    Ok(covariance_trace(
+        &scratch.matrix,
+        config.groups,
+        config.groups,
+    ))
+}
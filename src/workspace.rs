@@ -0,0 +1,135 @@
+//! Reusable buffers for extracting the features of many series.
+//!
+//! Every feature runs on the buffers of a [`Catch22Workspace`], growing them when a series needs
+//! more room. A workspace made with [`Catch22Workspace::with_capacity`] has all of them reserved,
+//! and the FFT planned, for series up to the given length, so that
+//! [`compute_all_into`](crate::compute_all_into) then runs without touching the heap.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::autocorrelation::{acf_into, first_zero_of};
+use crate::fft::FftBuffer;
+use crate::fluctuation::{FluctuationConfig, FluctuationScratch, Method};
+use crate::outliers::{OutlierConfig, SweepScratch};
+use crate::periodicity::PeriodicityScratch;
+use crate::symbolic::{SymbolicConfig, SymbolicScratch};
+use crate::transition::{TransitionConfig, TransitionScratch};
+
+/// Scratch space for the feature computations.
+#[derive(Default)]
+pub struct Catch22Workspace {
+    pub(crate) fft: FftBuffer,
+    pub(crate) acf: Vec<f64>,
+    /// A transformed copy of the series: residuals or embedding distances.
+    pub(crate) series: Vec<f64>,
+    /// Per-lag or per-frequency values: the automutual information curve, the power spectrum
+    /// and histogram marginals.
+    pub(crate) values: Vec<f64>,
+    pub(crate) window: Vec<f64>,
+    pub(crate) edges: Vec<f64>,
+    pub(crate) counts: Vec<usize>,
+    pub(crate) outliers: SweepScratch,
+    pub(crate) symbolic: SymbolicScratch,
+    pub(crate) fluctuation: FluctuationScratch,
+    pub(crate) transition: TransitionScratch,
+    pub(crate) periodicity: PeriodicityScratch,
+    pub(crate) shared: Shared,
+}
+
+/// Intermediates of the series being processed, computed by the first feature that needs them.
+#[derive(Default)]
+pub(crate) struct Shared {
+    /// The autocorrelation at every lag.
+    pub(crate) acf: Vec<f64>,
+    has_acf: bool,
+    /// Indices that sort the series in ascending order, and the sorted values.
+    pub(crate) order: Vec<usize>,
+    pub(crate) sorted: Vec<f64>,
+    has_order: bool,
+}
+
+impl Catch22Workspace {
+    /// An empty workspace; buffers are allocated as the features need them.
+    pub fn new() -> Self {
+        Catch22Workspace::default()
+    }
+
+    /// A workspace with every buffer reserved for series of up to `max_len` values.
+    pub fn with_capacity(max_len: usize) -> Self {
+        // autocorrelations are zero-padded to twice the next power of two
+        let n_fft = 2 * max_len.next_power_of_two();
+        let n_spectrum = max_len.next_power_of_two() / 2 + 1;
+        // the automutual information curve reaches lag 40
+        let n_values = n_spectrum.max(40);
+        // the joint histogram of CO_HistogramAMI_even_2_5
+        let n_counts = max_len.max(25);
+
+        Catch22Workspace {
+            fft: FftBuffer::with_capacity(n_fft),
+            acf: Vec::with_capacity(max_len),
+            series: Vec::with_capacity(max_len),
+            values: Vec::with_capacity(n_values),
+            window: Vec::with_capacity(max_len),
+            edges: Vec::with_capacity(6),
+            counts: Vec::with_capacity(n_counts),
+            outliers: SweepScratch::with_capacity(max_len, &OutlierConfig::default()),
+            symbolic: SymbolicScratch::with_capacity(max_len, &SymbolicConfig::default()),
+            fluctuation: FluctuationScratch::with_capacity(
+                max_len,
+                &FluctuationConfig::new(Method::RescaledRange),
+            ),
+            transition: TransitionScratch::with_capacity(max_len, &TransitionConfig::default()),
+            periodicity: PeriodicityScratch::with_capacity(max_len),
+            shared: Shared {
+                acf: Vec::with_capacity(max_len),
+                order: Vec::with_capacity(max_len),
+                sorted: Vec::with_capacity(max_len),
+                ..Shared::default()
+            },
+        }
+    }
+
+    /// Forgets the intermediates of the previous series.
+    pub(crate) fn start(&mut self) {
+        self.shared.has_acf = false;
+        self.shared.has_order = false;
+    }
+
+    /// Fills `shared.acf` with the autocorrelation of `a`, unless already done for this series.
+    pub(crate) fn share_acf(&mut self, a: &[f64]) {
+        if !self.shared.has_acf {
+            acf_into(a, a.len(), &mut self.fft, &mut self.shared.acf);
+            self.shared.has_acf = true;
+        }
+    }
+
+    /// Fills `shared.order` and `shared.sorted` for `a`, unless already done for this series.
+    pub(crate) fn share_order(&mut self, a: &[f64]) {
+        if !self.shared.has_order {
+            let Shared { order, sorted, .. } = &mut self.shared;
+            order.clear();
+            order.extend(0..a.len());
+            order.sort_unstable_by(|&i, &j| a[i].total_cmp(&a[j]));
+            sorted.clear();
+            sorted.extend(order.iter().map(|&i| a[i]));
+            self.shared.has_order = true;
+        }
+    }
+
+    /// First lag below `max_tau` at which the autocorrelation of `a` is not positive, `max_tau`
+    /// if none.
+    pub(crate) fn first_zero(&mut self, a: &[f64], max_tau: usize) -> usize {
+        self.share_acf(a);
+        // the autocorrelation up to a lag does not depend on how many lags are computed
+        let acf = &self.shared.acf;
+        let acf = &acf[..acf.len().min(max_tau.saturating_add(1))];
+        first_zero_of(acf).unwrap_or(max_tau).min(max_tau)
+    }
+}
+
+impl fmt::Debug for Catch22Workspace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Catch22Workspace").finish_non_exhaustive()
+    }
+}
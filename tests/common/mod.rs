@@ -0,0 +1,127 @@
+//! Deterministic series shared by the integration tests.
+
+// each test crate uses only some of the helpers
+#![allow(dead_code)]
+
+/// Xorshift64 generator, so that the test series are the same on every platform.
+pub struct Xorshift(pub u64);
+
+impl Xorshift {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform in [0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal by the Box-Muller transform.
+    pub fn normal(&mut self) -> f64 {
+        let u1 = self.uniform().max(1e-300);
+        let u2 = self.uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
+
+/// Uniform noise in [-0.5, 0.5).
+pub fn noise(n: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Xorshift(seed);
+    (0..n).map(|_| rng.uniform() - 0.5).collect()
+}
+
+/// Independent standard normal values.
+pub fn white_noise(n: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Xorshift(seed);
+    (0..n).map(|_| rng.normal()).collect()
+}
+
+/// AR(p) process driven by standard normal noise, starting from `p` zeros.
+pub fn ar(n: usize, phi: &[f64], seed: u64) -> Vec<f64> {
+    let mut rng = Xorshift(seed);
+    let mut x = vec![0.0; n];
+    for i in phi.len()..n {
+        let e = rng.normal();
+        x[i] = phi
+            .iter()
+            .enumerate()
+            .map(|(k, p)| p * x[i - 1 - k])
+            .sum::<f64>()
+            + e;
+    }
+    x
+}
+
+/// Two tones of incommensurate frequencies, scaled by `scale`, on a slow linear trend.
+pub fn mixture(n: usize, scale: f64) -> Vec<f64> {
+    (0..n)
+        .map(|i| scale * ((i as f64 * 0.15).sin() + 0.2 * (i as f64 * 1.3).cos()) + 0.01 * i as f64)
+        .collect()
+}
+
+/// A sine of period `period` with uniform noise of amplitude 0.1 on a slow linear trend.
+pub fn noisy_cycle(n: usize, period: f64, seed: u64) -> Vec<f64> {
+    let mut rng = Xorshift(seed);
+    (0..n)
+        .map(|i| {
+            let noise = rng.uniform() - 0.5;
+            (2.0 * std::f64::consts::PI * i as f64 / period).sin() + 0.2 * noise + 0.002 * i as f64
+        })
+        .collect()
+}
+
+/// Heavy-tailed noise rounded to quarters, so that it has ties, on a slow sine.
+pub fn heavy_tailed(n: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Xorshift(seed);
+    (0..n)
+        .map(|i| {
+            let u = rng.uniform();
+            ((u / (1.0 - u)).ln() * 4.0).round() / 4.0 + (i as f64 * 0.05).sin()
+        })
+        .collect()
+}
+
+/// `sin(omega * i)` for `i` in `0..n`.
+pub fn sine(n: usize, omega: f64) -> Vec<f64> {
+    (0..n).map(|i| (i as f64 * omega).sin()).collect()
+}
+
+/// RR intervals in seconds around 0.8 s, modulated at the respiratory and Mayer-wave
+/// frequencies.
+pub fn rr_intervals(n: usize, resp_hz: f64, mayer_hz: f64) -> Vec<f64> {
+    let mut t = 0.0;
+    let mut rr = Vec::with_capacity(n);
+    for _ in 0..n {
+        let x = 0.8
+            + 0.04 * (2.0 * std::f64::consts::PI * resp_hz * t).sin()
+            + 0.01 * (2.0 * std::f64::consts::PI * mayer_hz * t).sin();
+        rr.push(x);
+        t += x;
+    }
+    rr
+}
+
+/// Noisy sines (label 0) and random walks (label 1) of 200 values, `n` of each.
+pub fn sines_and_walks(n: usize, seed: u64) -> (Vec<Vec<f64>>, Vec<usize>) {
+    let mut series = Vec::new();
+    let mut labels = Vec::new();
+    for i in 0..n as u64 {
+        let e = noise(200, seed + 2 * i + 1);
+        let sine = (0..200)
+            .map(|t| (t as f64 * 0.25).sin() + 0.3 * e[t])
+            .collect();
+        let walk = e
+            .iter()
+            .scan(0.0, |s, v| {
+                *s += v;
+                Some(*s)
+            })
+            .collect();
+        series.extend([sine, walk]);
+        labels.extend([0, 1]);
+    }
+    (series, labels)
+}
@@ -0,0 +1,101 @@
+use catch22::ami::{AmiCurve, Estimator, ami_curve, kraskov_ami};
+use catch22::{compute, zscore};
+
+mod common;
+
+use common::{ar, mixture};
+
+#[test]
+fn test_reproduces_fmmi() {
+    let x = zscore(&mixture(400, 1.0));
+    let curve = ami_curve(&x, 40, Estimator::Gaussian);
+
+    assert_eq!(curve.lags.len(), 40);
+    let fmmi = curve.first_minimum().unwrap() as f64 - 1.0;
+    assert_eq!(fmmi, compute(&x, 11));
+}
+
+#[test]
+fn test_reproduces_histogram_ami() {
+    let x = zscore(&mixture(400, 1.0));
+    let curve = ami_curve(&x, 3, Estimator::Histogram { bins: 5 });
+
+    assert_eq!(curve.ami[1], compute(&x, 7));
+}
+
+#[test]
+fn test_kraskov_matches_gaussian_ar1() {
+    let x = ar(4000, &[0.8], 7);
+    let curve = ami_curve(&x, 3, Estimator::Kraskov { k: 4 });
+
+    for (lag, ami) in curve.lags.iter().zip(&curve.ami) {
+        let rho = 0.8f64.powi(*lag as i32);
+        let expected = -0.5 * (1.0 - rho * rho).ln();
+        assert!(
+            (ami - expected).abs() < 0.05,
+            "lag {lag}: {ami} vs {expected}"
+        );
+    }
+}
+
+#[test]
+fn test_kraskov_independent() {
+    let x = ar(3000, &[0.0], 11);
+    let curve = ami_curve(&x, 2, Estimator::Kraskov { k: 3 });
+
+    for ami in &curve.ami {
+        assert!(ami.abs() < 0.03, "{ami}");
+    }
+}
+
+#[test]
+fn test_kraskov_missing_values() {
+    let mut x = ar(200, &[0.5], 3);
+    x[20] = f64::NAN;
+    let curve = ami_curve(&x, 3, Estimator::Kraskov { k: 3 });
+    assert!(curve.ami.iter().all(|ami| ami.is_nan()));
+}
+
+#[test]
+fn test_kraskov_tied_values() {
+    // every point has at least k exact copies, so the k-th distance is 0
+    let x = (0..40).map(|i| (i % 2) as f64).collect::<Vec<_>>();
+    assert!(kraskov_ami(&x, 2, 3).is_finite());
+
+    let x = ar(500, &[0.7], 5)
+        .iter()
+        .map(|v| v.round())
+        .collect::<Vec<_>>();
+    let curve = ami_curve(&x, 5, Estimator::Kraskov { k: 3 });
+    assert!(curve.ami.iter().all(|ami| ami.is_finite()));
+}
+
+#[test]
+fn test_summaries() {
+    let curve = AmiCurve {
+        lags: vec![1, 2, 3, 4, 5],
+        ami: vec![1.0, 0.5, 0.25, 0.125, 0.0625],
+    };
+
+    assert_eq!(curve.first_minimum(), None);
+    assert_eq!(curve.area(), 1.9375);
+    assert!((curve.decay_rate() - 2.0f64.ln()).abs() < 1e-12);
+    // 1/e ≈ 0.368 lies between 0.5 at lag 2 and 0.25 at lag 3
+    let crossing = curve.first_1e_crossing().unwrap();
+    let expected = 2.0 + (0.5 - (-1.0f64).exp()) / 0.25;
+    assert!((crossing - expected).abs() < 1e-12);
+
+    let curve = AmiCurve {
+        lags: vec![1, 2, 3, 4],
+        ami: vec![1.0, 0.3, 0.4, 0.2],
+    };
+    assert_eq!(curve.first_minimum(), Some(2));
+}
+
+#[test]
+fn test_lag_clipped_to_length() {
+    let x = zscore(&mixture(400, 1.0));
+    let curve = ami_curve(&x[..10], 40, Estimator::Gaussian);
+
+    assert_eq!(curve.lags, (1..=8).collect::<Vec<usize>>());
+}
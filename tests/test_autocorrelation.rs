@@ -0,0 +1,98 @@
+use catch22::autocorrelation::{Acf, acf, pacf};
+use catch22::{compute, zscore};
+
+mod common;
+
+use common::ar;
+
+#[test]
+fn test_acf_matches_direct_sum() {
+    let x = [1.0, 3.0, 2.0, 5.0, 4.0, 4.0, 1.0, 0.0, 2.0];
+    let ac = acf(&x, 20);
+
+    assert_eq!(ac.values.len(), x.len());
+    assert_eq!(ac.n, x.len());
+
+    let m = x.iter().sum::<f64>() / x.len() as f64;
+    let c0 = x.iter().map(|v| (v - m).powi(2)).sum::<f64>();
+    for k in 0..x.len() {
+        let ck = (0..x.len() - k)
+            .map(|i| (x[i] - m) * (x[i + k] - m))
+            .sum::<f64>();
+        assert!((ac.values[k] - ck / c0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_summaries_reproduce_features() {
+    let x = zscore(
+        &(0..300)
+            .map(|i| (i as f64 * 0.2).sin() + 0.1 * (i as f64 * 1.7).cos())
+            .collect::<Vec<f64>>(),
+    );
+    let ac = acf(&x, x.len());
+
+    assert_eq!(ac.first_minimum().unwrap() as f64, compute(&x, 6));
+    assert_eq!(ac.first_1e_crossing().unwrap(), compute(&x, 5));
+    let zero = ac.first_zero().unwrap();
+    assert!(ac.values[zero - 1] > 0.0 && ac.values[zero] <= 0.0);
+}
+
+#[test]
+fn test_pacf_cuts_off_for_ar2() {
+    let x = ar(20000, &[0.5, -0.3], 3);
+    let p = pacf(&x, 6);
+    let bound = acf(&x, 6).white_noise_bound(0.99);
+
+    assert_eq!(p[0], 1.0);
+    // the lag-2 partial autocorrelation of an AR(2) is its second coefficient
+    assert!((p[2] + 0.3).abs() < 0.02, "{}", p[2]);
+    for (k, p_k) in p.iter().enumerate().skip(3) {
+        assert!(p_k.abs() < bound, "lag {k}: {p_k}");
+    }
+}
+
+#[test]
+fn test_pacf_of_ar1_decay() {
+    let r = 0.6f64;
+    // first lag of the PACF is the first autocorrelation
+    let x = ar(5000, &[r, 0.0], 9);
+    assert_eq!(pacf(&x, 3)[1], acf(&x, 3).values[1]);
+}
+
+#[test]
+fn test_bounds() {
+    let ac = Acf {
+        values: vec![1.0, 0.5, 0.25],
+        n: 100,
+    };
+
+    assert!((ac.white_noise_bound(0.95) - 0.196).abs() < 1e-3);
+    let bounds = ac.bartlett_bounds(0.95);
+    assert_eq!(bounds[0], 0.0);
+    assert!((bounds[1] - 0.196).abs() < 1e-3);
+    assert!((bounds[2] - 0.196 * 1.5f64.sqrt()).abs() < 1e-3);
+}
+
+#[test]
+fn test_ljung_box() {
+    let ac = Acf {
+        values: vec![1.0, 0.5, 0.25],
+        n: 10,
+    };
+
+    let q = 10.0 * 12.0 * (0.25 / 9.0 + 0.0625 / 8.0);
+    assert!((ac.ljung_box(2) - q).abs() < 1e-12);
+    assert_eq!(ac.ljung_box(5), ac.ljung_box(2));
+}
+
+#[test]
+fn test_first_zero_stops_at_nan() {
+    // the autocorrelation of a constant series is NaN at every lag
+    let constant = [2.0; 10];
+    assert_eq!(acf(&constant, 5).first_zero(), Some(0));
+    assert!(compute(&constant, 9).is_nan());
+
+    let ramp = (1..=10).map(f64::from).collect::<Vec<_>>();
+    assert_eq!(compute(&ramp, 9), 0.0);
+}
@@ -0,0 +1,144 @@
+use catch22::classify::{Catch22Classifier, ClassifierConfig, Metric, Scaler};
+use catch22::{Error, FeatureSet};
+
+mod common;
+
+use common::sines_and_walks;
+
+#[test]
+fn test_knn_and_centroid() {
+    let (train, labels) = sines_and_walks(10, 1);
+    let (test, truth) = sines_and_walks(5, 1000);
+
+    let configs = [
+        ClassifierConfig::default(),
+        ClassifierConfig::default().with_knn(3),
+        ClassifierConfig::default()
+            .with_knn(5)
+            .with_metric(Metric::Manhattan),
+        ClassifierConfig::default().with_metric(Metric::Cosine),
+        ClassifierConfig::default().with_nearest_centroid(),
+    ];
+    for config in configs {
+        let classifier = Catch22Classifier::fit_with(&train, &labels, &config).unwrap();
+        assert_eq!(classifier.classes(), [0, 1]);
+        for (x, &label) in test.iter().zip(&truth) {
+            assert_eq!(classifier.predict(x), label, "{config:?}");
+            let proba = classifier.predict_proba(x);
+            assert!((proba.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert!(proba[label] >= 0.5);
+        }
+    }
+}
+
+#[test]
+fn test_knn_probabilities_are_vote_shares() {
+    let (train, labels) = sines_and_walks(4, 7);
+    let config = ClassifierConfig::default().with_knn(4);
+    let classifier = Catch22Classifier::fit_with(&train, &labels, &config).unwrap();
+    for p in classifier.predict_proba(&train[0]) {
+        assert_eq!((p * 4.0).fract(), 0.0);
+    }
+}
+
+#[test]
+fn test_scaler_ignores_missing_and_constant_features() {
+    // three vectors of three features: regular, constant, and NaN but for one value
+    let vectors = [1.0, 5.0, f64::NAN, 2.0, 5.0, 4.0, 3.0, 5.0, f64::NAN];
+    let scaler = Scaler::fit(&vectors, 3);
+    assert_eq!(scaler.mean, [2.0, 5.0, 4.0]);
+    assert_eq!(scaler.std[1..], [0.0, 0.0]);
+
+    let mut v = [f64::NAN, 7.0, 4.0];
+    scaler.transform(&mut v);
+    assert_eq!(v, [0.0, 0.0, 0.0]);
+
+    let mut v = [3.0, f64::INFINITY, 1.0];
+    scaler.transform(&mut v);
+    assert_eq!(v, [(1.5f64).sqrt(), 0.0, 0.0]);
+}
+
+#[test]
+fn test_short_and_missing_series_give_finite_vectors() {
+    // too short for most features: every feature vector must still be usable
+    let (mut train, mut labels) = sines_and_walks(3, 3);
+    train.push(vec![1.0, 2.0, 3.0]);
+    labels.push(1);
+
+    let classifier = Catch22Classifier::fit(&train, &labels).unwrap();
+    let v = classifier.transform(&[4.0, 1.0]);
+    assert!(v.iter().all(|x| x.is_finite()));
+    let _ = classifier.predict(&[4.0, 1.0]);
+
+    let mut x = train[0].clone();
+    x[10] = f64::NAN;
+    assert!(classifier.transform(&x).iter().all(|&v| v == 0.0));
+}
+
+#[test]
+fn test_invalid_training_sets() {
+    let (train, labels) = sines_and_walks(2, 5);
+    assert!(matches!(
+        Catch22Classifier::fit(&train, &labels[1..]),
+        Err(Error::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        Catch22Classifier::<usize>::fit(&Vec::<Vec<f64>>::new(), &[]),
+        Err(Error::InvalidParameter(_))
+    ));
+    let config = ClassifierConfig::default().with_knn(0);
+    assert!(Catch22Classifier::fit_with(&train, &labels, &config).is_err());
+    let config = ClassifierConfig::default().with_features(FeatureSet::empty());
+    assert!(Catch22Classifier::fit_with(&train, &labels, &config).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_save_and_load() {
+    let (train, labels) = sines_and_walks(5, 11);
+    let names = labels
+        .iter()
+        .map(|&l| if l == 0 { "sine" } else { "walk" }.to_string())
+        .collect::<Vec<_>>();
+    let config = ClassifierConfig::default()
+        .with_knn(3)
+        .with_features(FeatureSet::from_keyword("autocorrelation"));
+    let classifier = Catch22Classifier::fit_with(&train, &names, &config).unwrap();
+
+    let json = classifier.save();
+    let loaded = Catch22Classifier::<String>::load(&json).unwrap();
+    assert_eq!(loaded, classifier);
+    assert_eq!(loaded.predict(&train[1]), "walk");
+
+    let json = json.replacen(r#""version":1"#, r#""version":2"#, 1);
+    assert!(matches!(
+        Catch22Classifier::<String>::load(&json),
+        Err(Error::InvalidModel(_))
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_load_rejects_inconsistent_models() {
+    let (train, labels) = sines_and_walks(3, 13);
+    let classifier = Catch22Classifier::fit(&train, &labels).unwrap();
+    let stored: serde_json::Value = serde_json::from_str(&classifier.save()).unwrap();
+
+    let tamper = |edit: &dyn Fn(&mut serde_json::Value)| {
+        let mut json = stored.clone();
+        edit(&mut json);
+        Catch22Classifier::<usize>::load(&json.to_string())
+    };
+    let edits: [&dyn Fn(&mut serde_json::Value); 6] = [
+        &|j| j["labels"][1] = 7.into(),
+        &|j| j["labels"] = serde_json::json!([]),
+        &|j| j["classes"] = serde_json::json!([]),
+        &|j| j["vectors"].as_array_mut().unwrap().truncate(5),
+        &|j| j["scaler"]["std"].as_array_mut().unwrap().truncate(5),
+        &|j| j["centroids"].as_array_mut().unwrap().push(0.0.into()),
+    ];
+    for edit in edits {
+        assert!(matches!(tamper(edit), Err(Error::InvalidModel(_))));
+    }
+    assert!(tamper(&|_| {}).is_ok());
+}
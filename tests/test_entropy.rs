@@ -0,0 +1,121 @@
+use catch22::entropy::{
+    approximate_entropy, coarse_grain, multiscale_entropy, permutation_entropy, sample_entropy,
+};
+use catch22::{EntropyOptions, FEATURE_NAMES, Options, compute_all};
+
+mod common;
+
+use common::noise;
+
+fn matches(a: &[f64], i: usize, j: usize, dim: usize, r: f64) -> bool {
+    (0..dim).all(|k| (a[i + k] - a[j + k]).abs() <= r)
+}
+
+fn naive_sample_entropy(a: &[f64], m: usize, r: f64) -> f64 {
+    let n = a.len() - m;
+    let (mut b, mut c) = (0, 0);
+    for i in 0..n {
+        for j in i + 1..n {
+            if matches(a, i, j, m, r) {
+                b += 1;
+            }
+            if matches(a, i, j, m + 1, r) {
+                c += 1;
+            }
+        }
+    }
+    -(c as f64 / b as f64).ln()
+}
+
+fn naive_phi(a: &[f64], dim: usize, r: f64) -> f64 {
+    let n = a.len() - dim + 1;
+    (0..n)
+        .map(|i| {
+            let c = (0..n).filter(|&j| matches(a, i, j, dim, r)).count();
+            (c as f64 / n as f64).ln()
+        })
+        .sum::<f64>()
+        / n as f64
+}
+
+#[test]
+fn test_sample_and_approximate_entropy() {
+    let x = noise(500, 11)
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (i as f64 * 0.2).sin() + e)
+        .collect::<Vec<f64>>();
+
+    for m in 1..=3 {
+        for r in [0.1, 0.2, 0.5] {
+            let fast = sample_entropy(&x, m, r);
+            assert!((fast - naive_sample_entropy(&x, m, r)).abs() < 1e-12);
+
+            let fast = approximate_entropy(&x, m, r);
+            let naive = naive_phi(&x, m, r) - naive_phi(&x, m + 1, r);
+            assert!((fast - naive).abs() < 1e-12);
+        }
+    }
+
+    // a sine is more regular than noise
+    let sine = (0..500)
+        .map(|i| (i as f64 * 0.2).sin())
+        .collect::<Vec<f64>>();
+    assert!(sample_entropy(&sine, 2, 0.2) < sample_entropy(&noise(500, 3), 2, 0.2 * 0.29));
+    assert!(sample_entropy(&x[..2], 2, 0.2).is_nan());
+}
+
+#[test]
+fn test_permutation_entropy() {
+    let ramp = (0..100).map(|i| i as f64).collect::<Vec<f64>>();
+    assert_eq!(permutation_entropy(&ramp, 3, 1), 0.0);
+
+    let x = noise(20000, 5);
+    let h = permutation_entropy(&x, 3, 1);
+    assert!((h - 6.0f64.ln()).abs() < 0.01);
+    let h = permutation_entropy(&x, 4, 2);
+    assert!((h - 24.0f64.ln()).abs() < 0.02);
+
+    assert!(permutation_entropy(&x, 1, 1).is_nan());
+    assert!(permutation_entropy(&x, 3, 0).is_nan());
+}
+
+#[test]
+fn test_multiscale_entropy() {
+    let x = noise(1000, 9);
+    assert_eq!(coarse_grain(&[1.0, 2.0, 3.0, 4.0, 5.0], 2), vec![1.5, 3.5]);
+
+    let mse = multiscale_entropy(&x, 2, 0.05, 4);
+    assert_eq!(mse.len(), 4);
+    assert_eq!(mse[0], sample_entropy(&x, 2, 0.05));
+    // white noise loses entropy as the coarse-grained variance shrinks
+    assert!(mse[3] < mse[0]);
+}
+
+#[test]
+fn test_missing_values_give_nan() {
+    let mut x = noise(200, 5);
+    x[50] = f64::NAN;
+    assert!(sample_entropy(&x, 2, 0.1).is_nan());
+    assert!(approximate_entropy(&x, 2, 0.1).is_nan());
+    assert!(multiscale_entropy(&x, 2, 0.1, 3).iter().all(|h| h.is_nan()));
+}
+
+#[test]
+fn test_compute_all_with_entropy() {
+    let x = catch22::zscore(&noise(300, 2));
+
+    let features = compute_all(&x, &Options::default());
+    assert_eq!(features.len(), FEATURE_NAMES.len());
+    assert_eq!(features.get("CO_trev_1_num"), Some(catch22::compute(&x, 8)));
+
+    let options = Options::default().with_entropy(EntropyOptions::default());
+    let features = compute_all(&x, &options);
+    assert_eq!(features.len(), FEATURE_NAMES.len() + 3 + 5);
+    assert_eq!(
+        features.get("EN_SampEn_m2"),
+        Some(sample_entropy(&x, 2, 0.2 * catch22::compute(&x, 23)))
+    );
+    assert!(features.get("EN_PermEn_d3_tau1").unwrap() > 1.7);
+    assert!(features.get("EN_MSE_m2_scale5").is_some());
+}
@@ -0,0 +1,39 @@
+use catch22::{
+    FEATURE_NAMES, FeatureMode, FeatureSet, N_CATCH22, N_CATCH24, N_FEATURES, Options, compute,
+    compute_all,
+};
+
+#[test]
+fn test_mode_lengths() {
+    assert_eq!(FeatureMode::Catch22.n_features(), N_CATCH22);
+    assert_eq!(FeatureMode::Catch24.n_features(), N_CATCH24);
+    assert_eq!(FeatureMode::Catch24PlusSlope.n_features(), N_FEATURES);
+    assert_eq!(FEATURE_NAMES.len(), N_FEATURES);
+    assert_eq!(FeatureMode::default(), FeatureMode::Catch24PlusSlope);
+
+    // the names pycatch22 appends with catch24=True
+    assert_eq!(
+        &FeatureMode::Catch24.names()[N_CATCH22..],
+        ["DN_Mean", "DN_Spread_Std"]
+    );
+    assert_eq!(FeatureSet::from(FeatureMode::Catch22).len(), N_CATCH22);
+}
+
+#[test]
+fn test_compute_all_honors_mode() {
+    let x = (0..200)
+        .map(|i| (i as f64 * 0.3).sin() + i as f64 * 0.01)
+        .collect::<Vec<f64>>();
+
+    for mode in [
+        FeatureMode::Catch22,
+        FeatureMode::Catch24,
+        FeatureMode::Catch24PlusSlope,
+    ] {
+        let features = compute_all(&x, &Options::default().with_mode(mode));
+        assert_eq!(features.names, mode.names());
+        for (i, value) in features.values.iter().enumerate() {
+            assert_eq!(value.to_bits(), compute(&x, i).to_bits());
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use catch22::{Error, FEATURE_NAMES, Feature, FeatureMode, FeatureSet, compute, compute_set};
+
+mod common;
+
+use common::noise;
+
+#[test]
+fn test_compute_set_matches_compute() {
+    let x = noise(300, 17)
+        .iter()
+        .enumerate()
+        .map(|(i, v)| v + (i as f64 * 0.2).sin())
+        .collect::<Vec<f64>>();
+
+    let sets = [
+        FeatureSet::all(),
+        FeatureSet::from_indices([5, 9, 20]).unwrap(),
+        FeatureSet::from_indices([1, 15]).unwrap(),
+        FeatureSet::from(Feature::TransitionMatrix),
+    ];
+    for set in sets {
+        let features = compute_set(&x, &set);
+        assert_eq!(features.len(), set.len());
+        for (feature, (name, value)) in set.iter().zip(features.iter()) {
+            assert_eq!(name, FEATURE_NAMES[feature.index()]);
+            assert_eq!(
+                value.to_bits(),
+                compute(&x, feature.index()).to_bits(),
+                "{name}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_builders() {
+    let by_name = FeatureSet::from_names(["CO_f1ecac", "acf_first_min"]).unwrap();
+    assert_eq!(
+        by_name.iter().collect::<Vec<_>>(),
+        [Feature::AcfTimescale, Feature::AcfFirstMin]
+    );
+    assert_eq!(by_name, FeatureSet::from_indices([6, 5]).unwrap());
+
+    let spectral = FeatureSet::from_keyword("spectral");
+    assert_eq!(
+        spectral,
+        [Feature::LowFreqPower, Feature::CentroidFreq]
+            .into_iter()
+            .collect()
+    );
+    assert_eq!((by_name | spectral).len(), 4);
+
+    let mut set = FeatureSet::empty();
+    assert!(set.insert(Feature::Mean));
+    assert!(!set.insert(Feature::Mean));
+    assert!(set.remove(Feature::Mean));
+    assert!(set.is_empty());
+    assert_eq!(FeatureSet::all().len(), FEATURE_NAMES.len());
+}
+
+#[test]
+fn test_unknown_features() {
+    assert_eq!(
+        FeatureSet::from_names(["CO_f1ecac", "CO_FirstMin"]),
+        Err(Error::UnknownFeature("CO_FirstMin".into()))
+    );
+    assert_eq!(
+        FeatureSet::from_indices([FEATURE_NAMES.len()]),
+        Err(Error::UnknownFeature(FEATURE_NAMES.len().to_string()))
+    );
+}
+
+#[test]
+fn test_compute_set_with_nan_does_not_panic() {
+    let mut x = (0..200)
+        .map(|i| (i as f64 * 0.3).sin())
+        .collect::<Vec<f64>>();
+    x[17] = f64::NAN;
+    // the shared sort must not panic; the slope feature rejects non-finite series on its own
+    let set = FeatureSet::from(FeatureMode::Catch24);
+    assert_eq!(compute_set(&x, &set).len(), set.len());
+}
+
+#[test]
+fn test_compute_set_on_short_series() {
+    for n in 0..8 {
+        let x = noise(n, 5);
+        let features = compute_set(&x, &FeatureSet::all());
+        assert_eq!(features.len(), FeatureSet::all().len());
+        for (feature, value) in FeatureSet::all().iter().zip(&features.values) {
+            if n < feature.info().min_length {
+                assert!(value.is_nan(), "{} {n}", feature.name());
+            }
+        }
+    }
+}
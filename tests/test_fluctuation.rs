@@ -0,0 +1,142 @@
+use catch22::Error;
+use catch22::fluctuation::{FluctuationConfig, Method, fluctuation};
+
+mod common;
+
+use common::white_noise;
+
+#[test]
+fn test_dfa_exponents() {
+    let noise = white_noise(4000, 7);
+    let walk = noise
+        .iter()
+        .scan(0.0, |acc, x| {
+            *acc += x;
+            Some(*acc)
+        })
+        .collect::<Vec<f64>>();
+
+    for order in 1..=3 {
+        let config = FluctuationConfig::new(Method::Dfa).with_order(order);
+
+        let fluct = fluctuation(&noise, &config).unwrap();
+        assert!(
+            (fluct.alpha - 0.5).abs() < 0.1,
+            "order {order}: {}",
+            fluct.alpha
+        );
+
+        let fluct = fluctuation(&walk, &config).unwrap();
+        assert!(
+            (fluct.alpha - 1.5).abs() < 0.15,
+            "order {order}: {}",
+            fluct.alpha
+        );
+    }
+}
+
+/// F(τ) with a least-squares line fitted to each window from scratch.
+fn direct_fluctuation(a: &[f64], tau: &[f64], method: Method) -> Vec<f64> {
+    let y_cs = a
+        .iter()
+        .scan(0.0, |acc, x| {
+            *acc += x;
+            Some(*acc)
+        })
+        .collect::<Vec<f64>>();
+
+    tau.iter()
+        .map(|&t| {
+            let t = t as usize;
+            let n_buffer = y_cs.len() / t;
+            let mut total = 0.0;
+            for y in y_cs.chunks_exact(t).take(n_buffer) {
+                let x = (1..=t).map(|k| k as f64).collect::<Vec<f64>>();
+                let (sx, sy) = (x.iter().sum::<f64>(), y.iter().sum::<f64>());
+                let sxx = x.iter().map(|x| x * x).sum::<f64>();
+                let sxy = x.iter().zip(y).map(|(x, y)| x * y).sum::<f64>();
+                let denom = t as f64 * sxx - sx * sx;
+                let m = (t as f64 * sxy - sx * sy) / denom;
+                let b = (sy * sxx - sx * sxy) / denom;
+                let res = x.iter().zip(y).map(|(x, y)| y - (m * x + b));
+                total += match method {
+                    Method::RescaledRange => {
+                        let (lo, hi) =
+                            res.fold((f64::MAX, f64::MIN), |(lo, hi), r| (lo.min(r), hi.max(r)));
+                        (hi - lo).powi(2)
+                    }
+                    Method::Dfa => res.map(|r| r * r).sum::<f64>(),
+                };
+            }
+            match method {
+                Method::RescaledRange => (total / n_buffer as f64).sqrt(),
+                Method::Dfa => (total / (n_buffer * t) as f64).sqrt(),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn test_windowed_fits_match_direct_fits() {
+    // a large offset makes the cumulative sum grow linearly, stressing the prefix sums
+    let x = white_noise(20000, 11)
+        .iter()
+        .map(|v| v + 1000.0)
+        .collect::<Vec<f64>>();
+
+    for method in [Method::RescaledRange, Method::Dfa] {
+        let fluct = fluctuation(&x, &FluctuationConfig::new(method)).unwrap();
+        let direct = direct_fluctuation(&x, &fluct.tau, method);
+        for (f, d) in fluct.f.iter().zip(&direct) {
+            assert!((f - d).abs() <= 1e-8 * d, "{method:?}: {f} != {d}");
+        }
+    }
+}
+
+#[test]
+fn test_scales_and_breakpoint() {
+    let x = white_noise(1000, 3);
+    let fluct = fluctuation(&x, &FluctuationConfig::new(Method::RescaledRange)).unwrap();
+
+    assert_eq!(fluct.tau.len(), fluct.f.len());
+    assert_eq!(fluct.tau[0], 5.0);
+    assert_eq!(*fluct.tau.last().unwrap(), 500.0);
+    assert!(fluct.tau.windows(2).all(|w| w[0] < w[1]));
+    assert!(fluct.breakpoint >= 5 && fluct.breakpoint < fluct.tau.len() - 5);
+    assert_eq!(fluct.breakpoint_prop(), catch22::compute(&x, 16));
+
+    let custom = FluctuationConfig::new(Method::Dfa)
+        .with_tau_range(10, 200)
+        .with_steps(20);
+    let fluct = fluctuation(&x, &custom).unwrap();
+    assert_eq!(fluct.tau[0], 10.0);
+    assert_eq!(*fluct.tau.last().unwrap(), 200.0);
+    assert!(fluct.tau.len() <= 20);
+}
+
+#[test]
+fn test_errors() {
+    let x = white_noise(30, 1);
+    assert!(matches!(
+        fluctuation(&x, &FluctuationConfig::new(Method::Dfa)),
+        Err(Error::NotEnoughScales { .. })
+    ));
+    assert!(matches!(
+        fluctuation(&x[..8], &FluctuationConfig::new(Method::Dfa)),
+        Err(Error::TooShort { .. })
+    ));
+    assert!(matches!(
+        fluctuation(&x, &FluctuationConfig::new(Method::Dfa).with_order(4)),
+        Err(Error::InvalidParameter(_))
+    ));
+    for min_points in [0, 1] {
+        let config = FluctuationConfig {
+            min_points,
+            ..FluctuationConfig::new(Method::Dfa)
+        };
+        assert!(matches!(
+            fluctuation(&x, &config),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+use catch22::Error;
+use catch22::histogram::{BinRule, Histogram, Histogram2d, bin_index};
+
+#[test]
+fn test_explicit_edges() {
+    let edges = [0.0, 1.0, 2.5, 4.0];
+    let hist = Histogram::with_edges(&[-1.0, 0.0, 0.5, 1.0, 2.4, 2.5, 4.0, 4.1], &edges).unwrap();
+
+    // the last bin is closed, values outside are dropped
+    assert_eq!(hist.counts, vec![2, 2, 2]);
+    assert_eq!(hist.total(), 6);
+    assert_eq!(hist.centers(), vec![0.5, 1.75, 3.25]);
+    assert_eq!(hist.bin(2.5), Some(2));
+    assert_eq!(hist.bin(-0.1), None);
+    assert_eq!(bin_index(&edges, 0.999), Some(0));
+
+    let density = hist.density();
+    let integral = density
+        .iter()
+        .zip(hist.widths())
+        .map(|(d, w)| d * w)
+        .sum::<f64>();
+    assert!((integral - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_equal_width() {
+    let x = (0..100).map(|i| i as f64 / 10.0).collect::<Vec<f64>>();
+    let hist = Histogram::equal_width(&x, 4).unwrap();
+
+    assert_eq!(hist.edges.len(), 5);
+    assert_eq!(hist.edges[0], 0.0);
+    assert_eq!(hist.edges[4], 9.9);
+    assert_eq!(hist.total(), 100);
+    assert_eq!(hist.probabilities().iter().sum::<f64>(), 1.0);
+
+    // arithmetic assignment agrees with the binary search away from the edges
+    for &v in &x {
+        let from_edges = hist.bin(v).unwrap();
+        let w = hist.widths()[0];
+        let arithmetic = (((v - hist.edges[0]) / w) as usize).min(3);
+        if (v - hist.edges[from_edges]).abs() > 1e-9 {
+            assert_eq!(from_edges, arithmetic);
+        }
+    }
+}
+
+#[test]
+fn test_rules() {
+    let x = (0..1000)
+        .map(|i| (i as f64 * 0.37).sin() * 3.0)
+        .collect::<Vec<f64>>();
+
+    assert_eq!(BinRule::Sturges.n_bins(&x), 11);
+    let scott = BinRule::Scott.n_bins(&x);
+    let fd = BinRule::FreedmanDiaconis.n_bins(&x);
+    assert!(scott > 5 && scott < 40, "{scott}");
+    assert!(fd > 5 && fd < 40, "{fd}");
+    assert_eq!(
+        Histogram::with_rule(&x, BinRule::Scott).unwrap().n_bins(),
+        scott
+    );
+
+    assert_eq!(BinRule::Scott.n_bins(&[2.0; 10]), 0);
+    assert_eq!(
+        Histogram::with_rule(&[2.0; 10], BinRule::Sturges),
+        Err(Error::ConstantSeries)
+    );
+}
+
+#[test]
+fn test_joint() {
+    let x = [0.1, 0.2, 1.5, 1.7, 0.3, 1.1];
+    let y = [0.5, 1.5, 1.5, 0.5, 0.5, 1.9];
+    let edges = [0.0, 1.0, 2.0];
+    let joint = Histogram2d::with_edges(&x, &y, &edges, &edges).unwrap();
+
+    assert_eq!(joint.counts, vec![vec![2, 1], vec![1, 2]]);
+    assert_eq!(joint.probabilities()[0][0], 2.0 / 6.0);
+    assert_eq!(joint.density()[1][1], 2.0 / 6.0);
+
+    // identical variables: the mutual information is the entropy of the marginal
+    let joint = Histogram2d::with_edges(&x, &x, &edges, &edges).unwrap();
+    assert!((joint.mutual_information() - 2.0f64.ln()).abs() < 1e-12);
+
+    assert!(Histogram2d::with_edges(&x, &y[..3], &edges, &edges).is_err());
+}
+
+#[test]
+fn test_invalid_edges() {
+    assert!(matches!(
+        Histogram::with_edges(&[1.0], &[0.0, 1.0, 1.0]),
+        Err(Error::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        Histogram::with_edges(&[1.0], &[0.0]),
+        Err(Error::InvalidParameter(_))
+    ));
+    assert!(Histogram::equal_width(&[1.0], 0).is_err());
+}
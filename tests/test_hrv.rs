@@ -0,0 +1,112 @@
+use catch22::TimeUnit;
+use catch22::hrv;
+
+mod common;
+
+use common::rr_intervals;
+
+#[test]
+fn test_time_domain() {
+    let rr = vec![0.80, 0.855, 0.845, 0.905, 0.88, 0.95];
+    let td = hrv::time_domain(&rr, TimeUnit::Seconds);
+
+    let d: [f64; 5] = [55.0, -10.0, 60.0, -25.0, 70.0];
+    let rmssd = (d.iter().map(|x| x * x).sum::<f64>() / 5.0).sqrt();
+    assert!((td.rmssd - rmssd).abs() < 1e-9);
+    assert!((td.mean_nn - 872.5).abs() < 1e-9);
+    assert_eq!(td.pnn50, 0.6);
+    assert_eq!(td.pnn20, 0.8);
+    assert_eq!(
+        hrv::pnn(&rr, 40.0, TimeUnit::Seconds),
+        catch22::compute(&rr, 12)
+    );
+
+    let millis = rr.iter().map(|x| x * 1000.0).collect::<Vec<_>>();
+    let td_ms = hrv::time_domain(&millis, TimeUnit::Milliseconds);
+    assert!((td.sdnn - td_ms.sdnn).abs() < 1e-9);
+    assert!((td.sdsd - td_ms.sdsd).abs() < 1e-9);
+}
+
+#[test]
+fn test_geometric() {
+    let rr = rr_intervals(600, 0.25, 0.1);
+    let index = hrv::triangular_index(&rr, TimeUnit::Seconds);
+    let tinn = hrv::tinn(&rr, TimeUnit::Seconds);
+
+    assert!(index > 1.0 && index < rr.len() as f64);
+    // the series spans 0.75 s to 0.85 s
+    assert!(tinn > 50.0 && tinn < 150.0, "tinn = {tinn}");
+}
+
+#[test]
+fn test_tinn_of_a_triangular_histogram() {
+    // bin counts 1, 2, 3, 2, 1: the fitted triangle spans the five bins
+    let w = hrv::HISTOGRAM_BIN_WIDTH_MS;
+    let rr = [0, 1, 1, 2, 2, 2, 3, 3, 4].map(|i| 800.0 + i as f64 * w);
+    assert_eq!(hrv::tinn(&rr, TimeUnit::Milliseconds), 5.0 * w);
+    assert_eq!(hrv::triangular_index(&rr, TimeUnit::Milliseconds), 3.0);
+
+    // a 30 s range of intervals is close to the cap of 4096 bins
+    let wide = (0..500)
+        .map(|i| 300.0 + (i as f64 * 0.37).sin().abs() * 30000.0)
+        .collect::<Vec<_>>();
+    assert!(hrv::tinn(&wide, TimeUnit::Milliseconds).is_finite());
+}
+
+#[test]
+fn test_geometric_rejects_invalid_intervals() {
+    let rr = rr_intervals(100, 0.25, 0.1);
+    // milliseconds passed as seconds span thousands of seconds
+    let mut invalid = vec![rr.iter().map(|x| x * 1000.0).collect::<Vec<_>>()];
+    for bad in [f64::NAN, f64::INFINITY] {
+        let mut x = rr.clone();
+        x[10] = bad;
+        invalid.push(x);
+    }
+    for x in &invalid {
+        assert!(hrv::triangular_index(x, TimeUnit::Seconds).is_nan());
+        assert!(hrv::tinn(x, TimeUnit::Seconds).is_nan());
+    }
+}
+
+#[test]
+fn test_poincare() {
+    let rr = rr_intervals(300, 0.25, 0.1);
+    let p = hrv::poincare(&rr, TimeUnit::Seconds);
+    let sdsd = hrv::sdsd(&rr, TimeUnit::Seconds);
+
+    assert!((p.sd1 - sdsd / 2.0f64.sqrt()).abs() < 1e-9);
+    assert!(p.sd2 > p.sd1);
+}
+
+#[test]
+fn test_frequency_domain() {
+    let config = hrv::FrequencyConfig::default();
+
+    let respiratory =
+        hrv::frequency_domain(&rr_intervals(600, 0.25, 0.1), TimeUnit::Seconds, &config);
+    assert!(respiratory.hf > respiratory.lf);
+
+    let mayer = hrv::frequency_domain(&rr_intervals(600, 0.1, 0.25), TimeUnit::Seconds, &config);
+    assert!(mayer.lf > mayer.hf);
+    assert!(mayer.lf_hf() > 1.0);
+
+    // 40 ms amplitude sine has a variance of 800 ms²
+    assert!(
+        (respiratory.total - 800.0 - 50.0).abs() < 200.0,
+        "{respiratory:?}"
+    );
+}
+
+#[test]
+fn test_frequency_domain_rejects_short_segments() {
+    let rr = rr_intervals(600, 0.25, 0.1);
+    for segment_len in [0, 1] {
+        let config = hrv::FrequencyConfig {
+            segment_len,
+            ..Default::default()
+        };
+        let bands = hrv::frequency_domain(&rr, TimeUnit::Seconds, &config);
+        assert!(bands.total.is_nan(), "{bands:?}");
+    }
+}
@@ -0,0 +1,50 @@
+use std::path::Path;
+use std::process::Command;
+
+use catch22::autocorrelation::acf;
+
+mod common;
+
+use common::ar;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+/// Autocorrelations go through a forward and an inverse FFT: RustFFT's with `std`, the radix-2
+/// transform with `--no-default-features`. Both must match the direct sums to within 1e-12.
+#[test]
+fn test_fft_matches_direct_sums() {
+    for n in [9, 100, 1000, 5000] {
+        let x = ar(n, &[0.7], 3);
+        let ac = acf(&x, n);
+
+        let m = x.iter().sum::<f64>() / n as f64;
+        let c0 = x.iter().map(|v| (v - m).powi(2)).sum::<f64>();
+        for k in 0..n {
+            let ck = (0..n - k).map(|i| (x[i] - m) * (x[i + k] - m)).sum::<f64>();
+            assert!((ac.values[k] - ck / c0).abs() < 1e-12, "n = {n}, lag {k}");
+        }
+    }
+}
+
+#[test]
+#[ignore = "needs `rustup target add thumbv7em-none-eabihf`"]
+fn test_builds_without_std() {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std");
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "build",
+            "--lib",
+            "--no-default-features",
+            "--target",
+            TARGET,
+        ])
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .unwrap();
+
+    assert!(status.success(), "could not build for {TARGET}");
+}
@@ -0,0 +1,53 @@
+use catch22::{LagUnit, Options, TimeUnit, compute, compute_with};
+
+mod common;
+
+use common::sine;
+
+#[test]
+fn test_default_options_match_compute() {
+    let x = sine(200, 0.3);
+    for i in 0..22 {
+        let a = compute(&x, i);
+        let b = compute_with(&x, i, &Options::default());
+        assert!(a == b || (a.is_nan() && b.is_nan()), "feature {i}: {a} != {b}");
+    }
+}
+
+#[test]
+fn test_lags_in_seconds() {
+    let x = sine(500, 0.3);
+    let fs = 250.0;
+    let options = Options::default()
+        .with_sampling_rate(fs)
+        .with_lag_unit(LagUnit::Seconds);
+
+    for i in [5, 6, 21] {
+        let samples = compute(&x, i);
+        let seconds = compute_with(&x, i, &options);
+        assert!((samples / fs - seconds).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_centroid_in_hz() {
+    let fs = 250.0;
+    let x = sine(1024, 2.0 * std::f64::consts::PI * 10.0 / fs);
+    let options = Options::default().with_sampling_rate(fs);
+
+    let angular = compute(&x, 19);
+    let hz = compute_with(&x, 19, &options);
+
+    assert!((angular * fs / (2.0 * std::f64::consts::PI) - hz).abs() < 1e-9);
+    assert!((hz - 10.0).abs() < 0.5);
+}
+
+#[test]
+fn test_pnn_units() {
+    let seconds = vec![0.80, 0.85, 0.84, 0.90, 0.88, 0.95];
+    let millis = seconds.iter().map(|x| x * 1000.0).collect::<Vec<_>>();
+    let options = Options::default().with_value_unit(TimeUnit::Milliseconds);
+
+    assert_eq!(compute(&seconds, 12), compute_with(&millis, 12, &options));
+    assert_eq!(compute(&seconds, 12), 0.6);
+}
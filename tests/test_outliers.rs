@@ -0,0 +1,142 @@
+use catch22::outliers::{Direction, Normalization, OutlierConfig, outlier_sweep};
+use catch22::{Error, compute, zscore};
+
+mod common;
+
+use common::heavy_tailed;
+
+/// Direct evaluation of every threshold, as in the reference implementation.
+fn naive(a: &[f64], is_pos: bool, inc: f64, trim_tr: f64) -> f64 {
+    let a = a
+        .iter()
+        .map(|x| if is_pos { *x } else { -*x })
+        .collect::<Vec<f64>>();
+    let median = |v: &[f64]| {
+        let mut v = v.to_vec();
+        v.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        let n = v.len();
+        if n == 0 {
+            0.0
+        } else if n.is_multiple_of(2) {
+            (v[n / 2] + v[n / 2 - 1]) / 2.0
+        } else {
+            v[n / 2]
+        }
+    };
+
+    let tot = a.iter().filter(|&x| *x >= 0.0).count();
+    let max_val = a.iter().cloned().fold(f64::MIN, f64::max);
+    if max_val < inc {
+        return 0.0;
+    }
+    let n_thresh = ((max_val / inc) + 1.0) as usize;
+
+    let mut msdti3 = vec![0.0; n_thresh];
+    let mut msdti4 = vec![0.0; n_thresh];
+    for i in 0..n_thresh {
+        let r = (0..a.len())
+            .filter(|&j| a[j] >= i as f64 * inc)
+            .map(|j| (j + 1) as f64)
+            .collect::<Vec<f64>>();
+        msdti3[i] = (r.len().saturating_sub(1) as f64 * 100.0) / tot as f64;
+        msdti4[i] = median(&r) / (a.len() as f64 / 2.0) - 1.0;
+    }
+    let mj = msdti3.iter().rposition(|&p| p > trim_tr).unwrap_or(0);
+    median(&msdti4[..mj + 1])
+}
+
+#[test]
+fn test_matches_direct_evaluation() {
+    for (n, seed) in [(50, 1), (201, 2), (1000, 3), (1001, 4)] {
+        let x = zscore(&heavy_tailed(n, seed));
+        for (direction, is_pos) in [(Direction::Positive, true), (Direction::Negative, false)] {
+            for (inc, trim) in [(0.01, 2.0), (0.05, 2.0), (0.01, 10.0)] {
+                let config = OutlierConfig::default()
+                    .with_increment(inc)
+                    .with_trim_threshold(trim);
+                let sweep = outlier_sweep(&x, direction, &config).unwrap();
+                assert_eq!(sweep.median_position(), naive(&x, is_pos, inc, trim));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reproduces_features() {
+    let x = zscore(&heavy_tailed(500, 7));
+    let p = outlier_sweep(&x, Direction::Positive, &OutlierConfig::default()).unwrap();
+    let n = outlier_sweep(&x, Direction::Negative, &OutlierConfig::default()).unwrap();
+
+    assert_eq!(n.median_position(), compute(&x, 0));
+    assert_eq!(p.median_position(), compute(&x, 1));
+}
+
+#[test]
+fn test_raw_scale() {
+    let z = zscore(&heavy_tailed(300, 9));
+    let raw = z.iter().map(|x| 2500.0 + 400.0 * x).collect::<Vec<f64>>();
+
+    // auto-normalised raw data gives the z-scored result up to rounding
+    let auto = outlier_sweep(&raw, Direction::Positive, &OutlierConfig::default()).unwrap();
+    let expected = outlier_sweep(&z, Direction::Positive, &OutlierConfig::default()).unwrap();
+    assert!((auto.median_position() - expected.median_position()).abs() < 1e-2);
+    assert_eq!(auto.thresholds.len(), expected.thresholds.len());
+
+    let config = OutlierConfig::default().with_normalization(Normalization::Require);
+    assert_eq!(
+        outlier_sweep(&raw, Direction::Positive, &config),
+        Err(Error::NotNormalized)
+    );
+    assert!(outlier_sweep(&z, Direction::Positive, &config).is_ok());
+}
+
+#[test]
+fn test_sweep_contents() {
+    let x = zscore(&heavy_tailed(400, 11));
+    let sweep = outlier_sweep(&x, Direction::Positive, &OutlierConfig::default()).unwrap();
+
+    assert_eq!(sweep.thresholds[0], 0.0);
+    assert!(sweep.percent.windows(2).all(|w| w[0] >= w[1]));
+    assert!(
+        sweep
+            .median_position
+            .iter()
+            .all(|p| (-1.0..=1.0).contains(p))
+    );
+    assert!(sweep.percent[sweep.trim_limit()] > 2.0);
+    assert!(sweep.mean_interval[0] >= 1.0);
+}
+
+#[test]
+fn test_errors() {
+    let config = OutlierConfig::default();
+    assert_eq!(
+        outlier_sweep(&[1.0; 10], Direction::Positive, &config),
+        Err(Error::ConstantSeries)
+    );
+    assert!(matches!(
+        outlier_sweep(
+            &[1.0, 2.0],
+            Direction::Positive,
+            &config.with_increment(0.0)
+        ),
+        Err(Error::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_missing_values() {
+    let mut x = zscore(&heavy_tailed(300, 13));
+    x[10] = f64::NAN;
+    for normalization in [Normalization::Auto, Normalization::Off] {
+        let config = OutlierConfig::default().with_normalization(normalization);
+        for direction in [Direction::Positive, Direction::Negative] {
+            assert_eq!(
+                outlier_sweep(&x, direction, &config),
+                Err(Error::MissingValues)
+            );
+        }
+    }
+    assert!(compute(&x, 0).is_nan());
+    assert!(compute(&x, 1).is_nan());
+}
@@ -0,0 +1,82 @@
+use catch22::periodicity::{Detrend, PeriodicityConfig, periodicity};
+use catch22::{Error, compute, zscore};
+
+mod common;
+
+use common::noisy_cycle;
+
+#[test]
+fn test_reproduces_wang() {
+    let x = zscore(&noisy_cycle(500, 25.0, 5));
+    let p = periodicity(&x, &PeriodicityConfig::default()).unwrap();
+
+    assert_eq!((p.period().unwrap() - 1) as f64, compute(&x, 21));
+}
+
+#[test]
+fn test_all_candidates() {
+    let x = noisy_cycle(600, 20.0, 5);
+    let p = periodicity(&x, &PeriodicityConfig::default()).unwrap();
+
+    // multiples of the period up to n / 3
+    let periods = p
+        .candidates
+        .iter()
+        .map(|c| c.period)
+        .collect::<Vec<usize>>();
+    assert!(periods.len() >= 8, "{periods:?}");
+    for (k, period) in periods.iter().enumerate() {
+        assert!((*period as f64 - 20.0 * (k + 1) as f64).abs() <= 2.0);
+    }
+    for c in &p.candidates {
+        assert!(c.trough_lag < c.period);
+        assert!(c.peak >= 0.0 && c.prominence() >= 0.01);
+    }
+
+    assert!((p.spectral_period.unwrap() - 20.0).abs() < 1.0);
+    assert!(p.agrees_with_spectrum(0.1));
+}
+
+#[test]
+fn test_threshold_and_horizon() {
+    let x = noisy_cycle(600, 20.0, 5);
+
+    let p = periodicity(&x, &PeriodicityConfig::default().with_max_lag(50)).unwrap();
+    assert_eq!(p.candidates.len(), 2);
+
+    let p = periodicity(&x, &PeriodicityConfig::default().with_threshold(100.0)).unwrap();
+    assert!(p.candidates.is_empty());
+    assert_eq!(p.period(), None);
+}
+
+#[test]
+fn test_detrend_options() {
+    let x = noisy_cycle(400, 16.0, 5);
+    for detrend in [
+        Detrend::None,
+        Detrend::Constant,
+        Detrend::Linear,
+        Detrend::Spline,
+        Detrend::BSpline {
+            degree: 2,
+            n_breaks: 5,
+        },
+    ] {
+        let config = PeriodicityConfig::default().with_detrend(detrend);
+        let p = periodicity(&x, &config).unwrap();
+        assert!(
+            (p.period().unwrap() as f64 - 16.0).abs() <= 1.0,
+            "{detrend:?}"
+        );
+    }
+}
+
+#[test]
+fn test_errors() {
+    let config = PeriodicityConfig::default();
+    assert_eq!(
+        periodicity(&[1.0, 2.0], &config),
+        Err(Error::TooShort { len: 2, min: 3 })
+    );
+    assert_eq!(periodicity(&[3.0; 20], &config), Err(Error::ConstantSeries));
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "pipeline")]
+
+use std::process::Command;
+
+use catch22::pipeline::{Missing, PipelineSpec, Step, Window};
+use catch22::{Error, FeatureMode, FeatureSet, Options, compute_set_with, zscore};
+
+mod common;
+
+use common::mixture;
+
+const SPEC: &str = r#"
+preprocess = [
+    { step = "missing", strategy = "interpolate" },
+    { step = "zscore" },
+]
+
+[window]
+length = 100
+step = 40
+
+[features]
+names = ["CO_f1ecac", "acf_first_min"]
+keywords = ["spectral"]
+
+[options]
+sampling_rate = 10.0
+"#;
+
+#[test]
+fn test_toml_and_json_specs() {
+    let spec = PipelineSpec::from_toml(SPEC).unwrap();
+    assert_eq!(
+        spec.preprocess,
+        [
+            Step::Missing {
+                strategy: Missing::Interpolate
+            },
+            Step::ZScore
+        ]
+    );
+    assert_eq!(spec.window, Some(Window::new(100).with_step(40)));
+    assert_eq!(spec.features.features().len(), 4);
+    assert_eq!(spec.options, Options::default().with_sampling_rate(10.0));
+
+    let json = serde_json::to_string(&spec).unwrap();
+    assert_eq!(PipelineSpec::from_json(&json).unwrap(), spec);
+
+    // every section is optional
+    let spec = PipelineSpec::from_toml("").unwrap();
+    assert_eq!(spec, PipelineSpec::default());
+    assert_eq!(
+        spec.features.features(),
+        FeatureSet::from(FeatureMode::Catch24PlusSlope)
+    );
+}
+
+#[test]
+fn test_run_matches_compute() {
+    let spec = PipelineSpec::from_toml(SPEC).unwrap();
+    let x = mixture(300, 1.0);
+
+    let windows = spec.run(&x).unwrap();
+    assert_eq!(
+        windows.iter().map(|w| w.start).collect::<Vec<_>>(),
+        [0, 40, 80, 120, 160, 200]
+    );
+    for window in &windows {
+        let w = zscore(&x[window.start..window.start + 100]);
+        let expected = compute_set_with(&w, &spec.features.features(), &spec.options);
+        assert_eq!(window.length, 100);
+        assert_eq!(window.features, expected);
+    }
+}
+
+#[test]
+fn test_missing_values() {
+    let mut x = mixture(200, 1.0);
+    x[0] = f64::NAN;
+    x[50] = f64::NAN;
+    x[51] = f64::NAN;
+
+    let mut spec = PipelineSpec::default();
+    assert_eq!(spec.run(&x), Err(Error::MissingValues));
+
+    spec.preprocess = vec![Step::Missing {
+        strategy: Missing::Interpolate,
+    }];
+    let interpolated = spec.run(&x).unwrap();
+    let mut y = x.clone();
+    y[0] = x[1];
+    y[50] = x[49] + (x[52] - x[49]) / 3.0;
+    y[51] = x[49] + 2.0 * (x[52] - x[49]) / 3.0;
+    assert_eq!(
+        interpolated[0].features,
+        PipelineSpec::default().run(&y).unwrap()[0].features
+    );
+
+    spec.preprocess = vec![Step::Missing {
+        strategy: Missing::Drop,
+    }];
+    let dropped = spec.run(&x).unwrap();
+    let y = x
+        .iter()
+        .copied()
+        .filter(|v| !v.is_nan())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        dropped[0].features,
+        PipelineSpec::default().run(&y).unwrap()[0].features
+    );
+}
+
+#[test]
+fn test_windows_shortened_by_dropping() {
+    let mut x = mixture(10, 1.0);
+    x[1..9].fill(f64::NAN);
+    let spec = PipelineSpec {
+        preprocess: vec![Step::Missing {
+            strategy: Missing::Drop,
+        }],
+        ..PipelineSpec::default()
+    };
+    let windows = spec.run(&x).unwrap();
+    let set = spec.features.features();
+    assert_eq!(windows[0].features.len(), set.len());
+    for (feature, value) in set.iter().zip(&windows[0].features.values) {
+        let too_short = feature.info().min_length > 2;
+        assert_eq!(value.is_nan(), too_short, "{}", feature.name());
+    }
+}
+
+#[test]
+fn test_invalid_specs() {
+    assert!(matches!(
+        PipelineSpec::from_toml("[window]\nlength = 0"),
+        Err(Error::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        PipelineSpec::from_toml("[features]\nkeywords = [\"spectrum\"]"),
+        Err(Error::InvalidSpec(_))
+    ));
+    assert!(matches!(
+        PipelineSpec::from_toml("[features]\nnames = [\"CO_FirstMin\"]"),
+        Err(Error::InvalidSpec(_))
+    ));
+    assert!(matches!(
+        PipelineSpec::from_json(r#"{"preprocess": [{"step": "smooth"}]}"#),
+        Err(Error::InvalidSpec(_))
+    ));
+
+    let spec = PipelineSpec {
+        window: Some(Window::new(100)),
+        ..PipelineSpec::default()
+    };
+    assert_eq!(
+        spec.run(&mixture(50, 1.0)),
+        Err(Error::TooShort { len: 50, min: 100 })
+    );
+}
+
+#[test]
+fn test_cli() {
+    let dir = std::env::temp_dir().join(format!("catch22-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let spec = dir.join("spec.toml");
+    let data = dir.join("series.csv");
+    std::fs::write(&spec, SPEC).unwrap();
+    let x = mixture(300, 1.0);
+    let text = x
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    std::fs::write(&data, text).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_catch22"))
+        .arg("--spec")
+        .arg(&spec)
+        .arg(&data)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines[0],
+        "file,start,length,CO_f1ecac,CO_FirstMin_ac,SP_Summaries_welch_rect_area_5_1,SP_Summaries_welch_rect_centroid"
+    );
+    assert_eq!(lines.len(), 7);
+
+    let first = PipelineSpec::from_toml(SPEC).unwrap().run(&x).unwrap();
+    let values = lines[1]
+        .split(',')
+        .skip(3)
+        .map(|v| v.parse::<f64>().unwrap());
+    assert!(values.eq(first[0].features.values.iter().copied()));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,111 @@
+use catch22::quantile::{Method, median, quantile, quantiles};
+
+mod common;
+
+use common::Xorshift;
+
+const METHODS: [Method; 10] = [
+    Method::Type1,
+    Method::Type2,
+    Method::Type3,
+    Method::Type4,
+    Method::Type5,
+    Method::Type6,
+    Method::Type7,
+    Method::Type8,
+    Method::Type9,
+    Method::Matlab,
+];
+
+#[test]
+fn test_hyndman_fan_definitions() {
+    let x = [3.0, 1.0, 4.0, 2.0];
+    let expected = [
+        2.0,
+        2.0,
+        1.0,
+        1.2,
+        1.7,
+        1.5,
+        1.9,
+        1.3 + 1.0 / 3.0,
+        1.65,
+        1.7,
+    ];
+
+    for (method, e) in METHODS.iter().zip(expected) {
+        let q = quantiles(&x, &[0.3], *method)[0];
+        assert!((q - e).abs() < 1e-12, "{method:?}: {q} vs {e}");
+    }
+}
+
+#[test]
+fn test_discontinuous_ties() {
+    let x = [1.0, 2.0, 3.0, 4.0];
+
+    assert_eq!(quantiles(&x, &[0.5], Method::Type1), vec![2.0]);
+    assert_eq!(quantiles(&x, &[0.5], Method::Type2), vec![2.5]);
+    // round half to even order statistic
+    assert_eq!(
+        quantiles(&x, &[0.375, 0.5, 0.625], Method::Type3),
+        vec![2.0, 2.0, 2.0]
+    );
+}
+
+#[test]
+fn test_extremes_and_invalid() {
+    let x = [5.0, -1.0, 2.0, 8.0, 0.5];
+    for method in METHODS {
+        let q = quantiles(&x, &[0.0, 1.0], method);
+        assert_eq!(q[1], 8.0, "{method:?}");
+        assert!(q[0] == -1.0, "{method:?}: {}", q[0]);
+    }
+    assert!(quantiles(&x, &[1.5], Method::Type7)[0].is_nan());
+    assert!(quantile(&[], 0.5, Method::Type7).is_nan());
+    assert!(median(&[]).is_nan());
+}
+
+#[test]
+fn test_missing_values_give_nan() {
+    for x in [[1.0, f64::NAN, 3.0], [1.0, -f64::NAN, 3.0]] {
+        assert!(median(&x).is_nan());
+        assert!(quantile(&x, 0.5, Method::Type7).is_nan());
+        assert!(
+            quantiles(&x, &[0.0, 0.5, 1.0], Method::Matlab)
+                .iter()
+                .all(|q| q.is_nan())
+        );
+    }
+}
+
+#[test]
+fn test_matlab_at_sample_positions() {
+    // 10 * 0.25 - 0.5 lands exactly on the third value
+    let x = (1..=10).map(|i| i as f64).collect::<Vec<f64>>();
+    assert_eq!(quantiles(&x, &[0.25], Method::Matlab), vec![3.0]);
+}
+
+#[test]
+fn test_selection_matches_sort() {
+    let mut rng = Xorshift(17);
+    let x = (0..501)
+        .map(|_| ((rng.next_u64() % 1000) as f64 / 37.0).round())
+        .collect::<Vec<f64>>();
+    let p = [0.0, 0.01, 0.1, 0.25, 1.0 / 3.0, 0.5, 0.9, 0.999, 1.0];
+
+    for method in METHODS {
+        let sorted = quantiles(&x, &p, method);
+        for (pi, q) in p.iter().zip(&sorted) {
+            assert_eq!(quantile(&x, *pi, method), *q, "{method:?} at {pi}");
+        }
+    }
+
+    let mut sorted = x.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(median(&x), sorted[250]);
+    assert_eq!(median(&x[..500]), {
+        let mut v = x[..500].to_vec();
+        v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        (v[250] + v[249]) / 2.0
+    });
+}
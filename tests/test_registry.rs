@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use catch22::{FEATURE_NAMES, Feature, compute, compute_set};
+
+mod common;
+
+use common::noise;
+
+#[test]
+fn test_registry_matches_feature_names() {
+    for (i, feature) in Feature::ALL.into_iter().enumerate() {
+        let info = feature.info();
+        assert_eq!(feature.index(), i);
+        assert_eq!(Feature::from_index(i), Some(feature));
+        assert_eq!(info.feature, feature);
+        assert_eq!(info.name, FEATURE_NAMES[i]);
+        assert_eq!(feature.to_string(), FEATURE_NAMES[i]);
+        assert!(!info.description.is_empty());
+        assert!(!info.keywords.is_empty());
+        assert!(info.min_length >= 1);
+    }
+    assert_eq!(Feature::from_index(FEATURE_NAMES.len()), None);
+
+    let short_names = Feature::ALL
+        .iter()
+        .map(|f| f.short_name())
+        .collect::<HashSet<_>>();
+    assert_eq!(short_names.len(), Feature::ALL.len());
+}
+
+#[test]
+fn test_lookup_by_name() {
+    assert_eq!(
+        Feature::from_name("CO_FirstMin_ac"),
+        Some(Feature::AcfFirstMin)
+    );
+    assert_eq!(
+        Feature::from_name("acf_first_min"),
+        Some(Feature::AcfFirstMin)
+    );
+    assert_eq!(Feature::from_name("SD"), Some(Feature::Sd));
+    assert_eq!(Feature::from_name("CO_FirstMin"), None);
+}
+
+#[test]
+fn test_filter_by_keyword() {
+    let spectral = Feature::ALL
+        .into_iter()
+        .filter(|f| f.info().has_keyword("spectral"))
+        .collect::<Vec<_>>();
+    assert_eq!(spectral, [Feature::LowFreqPower, Feature::CentroidFreq]);
+}
+
+#[test]
+fn test_min_length() {
+    for feature in Feature::ALL {
+        let info = feature.info();
+        for seed in 1..10 {
+            let x = noise(info.min_length, seed * 7919);
+            let value = compute(&x, feature.index());
+            assert!(value.is_finite(), "{}: {value}", info.name);
+        }
+        for n in 0..info.min_length {
+            let value = compute_set(&noise(n, 7919), &feature.into()).values[0];
+            assert!(value.is_nan(), "{} on {n} values: {value}", info.name);
+        }
+    }
+}
+
+#[test]
+fn test_zscore_assumption() {
+    // features that do not assume z-scored input are invariant to an affine change of scale
+    let x = noise(500, 99);
+    let y = x.iter().map(|v| 3.0 * v + 5.0).collect::<Vec<f64>>();
+
+    for feature in Feature::ALL {
+        let info = feature.info();
+        let (a, b) = (compute(&x, feature.index()), compute(&y, feature.index()));
+        let invariant = (a - b).abs() <= 1e-9 * a.abs().max(1.0);
+        let location_or_scale = matches!(feature, Feature::Mean | Feature::Sd);
+        assert_eq!(
+            invariant,
+            !info.assumes_zscored && !location_or_scale,
+            "{}",
+            info.name
+        );
+    }
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "serde")]
+
+use catch22::{
+    EntropyOptions, Feature, FeatureMode, FeatureSet, Features, LagUnit, Options, compute_all,
+};
+
+#[test]
+fn test_features_round_trip() {
+    let x = (0..100)
+        .map(|i| (i as f64 * 0.4).sin())
+        .collect::<Vec<f64>>();
+    let mut features = compute_all(&x, &Options::default());
+    features.push("EN_SampEn_m2", f64::NAN);
+
+    let json = serde_json::to_string(&features).unwrap();
+    assert!(json.starts_with(r#"{"version":1,"features":{"DN_OutlierInclude_n_001_mdrmd":"#));
+    assert!(json.ends_with(r#""EN_SampEn_m2":null}}"#));
+
+    let loaded: Features = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.names, features.names);
+    for (a, b) in loaded.values.iter().zip(&features.values) {
+        assert!(a == b || (a.is_nan() && b.is_nan()));
+    }
+}
+
+#[test]
+fn test_features_version_is_checked() {
+    let err = serde_json::from_str::<Features>(r#"{"version":2,"features":{"DN_Mean":0.5}}"#)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unsupported features format version 2")
+    );
+
+    assert!(serde_json::from_str::<Features>(r#"{"features":{"DN_Mean":0.5}}"#).is_err());
+}
+
+#[test]
+fn test_features_names_are_checked() {
+    let load = |json: &str| serde_json::from_str::<Features>(json).map_err(|e| e.to_string());
+
+    let err = load(r#"{"version":1,"features":{"DN_Mean":0.5,"DN_Median":0.1}}"#).unwrap_err();
+    assert!(err.contains("unknown feature: DN_Median"), "{err}");
+    let err = load(r#"{"version":1,"features":{"DN_Mean":0.5,"DN_Mean":0.1}}"#).unwrap_err();
+    assert!(err.contains("repeated feature: DN_Mean"), "{err}");
+
+    let loaded = load(
+        r#"{"version":1,"features":{"EN_ApEn_m2":0.5,"EN_PermEn_d3_tau1":1.0,"EN_MSE_m2_scale4":0.1}}"#,
+    )
+    .unwrap();
+    assert_eq!(loaded.len(), 3);
+    assert!(load(r#"{"version":1,"features":{"EN_MSE_m2_scale":0.1}}"#).is_err());
+}
+
+#[test]
+fn test_options_round_trip() {
+    let options = Options::default()
+        .with_sampling_rate(128.0)
+        .with_lag_unit(LagUnit::Seconds)
+        .with_mode(FeatureMode::Catch24)
+        .with_entropy(EntropyOptions::default());
+
+    let json = serde_json::to_string(&options).unwrap();
+    assert!(json.contains(r#""mode":"catch24""#));
+    assert!(json.contains(r#""lag_unit":"seconds""#));
+    assert_eq!(serde_json::from_str::<Options>(&json).unwrap(), options);
+
+    // missing fields take their defaults
+    let options = serde_json::from_str::<Options>(r#"{"sampling_rate":250.0}"#).unwrap();
+    assert_eq!(options, Options::default().with_sampling_rate(250.0));
+}
+
+#[test]
+fn test_feature_names() {
+    assert_eq!(
+        serde_json::to_string(&Feature::AcfTimescale).unwrap(),
+        r#""CO_f1ecac""#
+    );
+    assert_eq!(
+        serde_json::from_str::<Feature>(r#""acf_first_min""#).unwrap(),
+        Feature::AcfFirstMin
+    );
+    assert!(serde_json::from_str::<Feature>(r#""CO_FirstMin""#).is_err());
+
+    let set = FeatureSet::from_keyword("spectral");
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(
+        json,
+        r#"["SP_Summaries_welch_rect_area_5_1","SP_Summaries_welch_rect_centroid"]"#
+    );
+    assert_eq!(serde_json::from_str::<FeatureSet>(&json).unwrap(), set);
+}
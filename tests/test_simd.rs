@@ -0,0 +1,56 @@
+#![cfg(feature = "simd")]
+
+use catch22::ami::gaussian_ami;
+use catch22::compute;
+
+mod common;
+
+use common::mixture;
+
+/// The documented bound on the difference between the vectorised and scalar sums of `terms`.
+fn tolerance(terms: impl Iterator<Item = f64>) -> f64 {
+    let (n, abs_sum) = terms.fold((0, 0.0), |(n, s), t| (n + 1, s + t.abs()));
+    2.0 * n as f64 * f64::EPSILON * abs_sum
+}
+
+#[test]
+fn test_mean_and_std_dev() {
+    // every remainder of the four-lane loop, and a long series
+    for n in (2..=13).chain([1001, 4099]) {
+        let x = mixture(n, 3.0);
+
+        let sum = x.iter().sum::<f64>();
+        let mean = sum / n as f64;
+        assert!((compute(&x, 22) - mean).abs() * n as f64 <= tolerance(x.iter().copied()));
+
+        let ss = x.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+        let sd = (ss / (n - 1) as f64).sqrt();
+        assert!((compute(&x, 23) - sd).abs() <= 1e-12 * sd, "n = {n}");
+    }
+}
+
+#[test]
+fn test_gaussian_ami() {
+    let x = mixture(997, 3.0);
+
+    for tau in 1..=40 {
+        let (a, b) = (&x[..x.len() - tau], &x[tau..]);
+        let ma = a.iter().sum::<f64>() / a.len() as f64;
+        let mb = b.iter().sum::<f64>() / b.len() as f64;
+        let nom = a
+            .iter()
+            .zip(b)
+            .map(|(p, q)| (p - ma) * (q - mb))
+            .sum::<f64>();
+        let da = a.iter().map(|p| (p - ma).powi(2)).sum::<f64>();
+        let db = b.iter().map(|q| (q - mb).powi(2)).sum::<f64>();
+        let r = nom / (da * db).sqrt();
+        let expected = -0.5 * (1.0 - r * r).ln();
+
+        let ami = gaussian_ami(&x, tau);
+        assert!(
+            (ami - expected).abs() <= 1e-12 * expected.abs().max(1.0),
+            "tau = {tau}: {ami} != {expected}"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use catch22::Error;
+use catch22::spline::{BSpline, splinefit};
+
+#[test]
+fn test_reproduces_polynomial() {
+    let x = (0..40)
+        .map(|i| {
+            let t = i as f64;
+            0.5 - 0.2 * t + 0.03 * t * t - 0.001 * t * t * t
+        })
+        .collect::<Vec<f64>>();
+    let spline = BSpline::fit(&x, 3, 4).unwrap();
+
+    for r in spline.residuals(&x) {
+        assert!(r.abs() < 1e-8, "{r}");
+    }
+    // derivatives of the cubic at t = 10
+    assert!((spline.eval_derivative(10.0, 1) - (-0.2 + 0.6 - 0.3)).abs() < 1e-8);
+    assert!((spline.eval_derivative(10.0, 2) - (0.06 - 0.06)).abs() < 1e-8);
+    assert!((spline.eval_derivative(10.0, 3) + 0.006).abs() < 1e-8);
+    assert_eq!(spline.eval_derivative(10.0, 4), 0.0);
+}
+
+#[test]
+fn test_fit_structure() {
+    let x = (0..101)
+        .map(|i| (i as f64 * 0.13).sin() + 0.01 * i as f64)
+        .collect::<Vec<f64>>();
+    let spline = BSpline::fit(&x, 2, 6).unwrap();
+
+    assert_eq!(spline.breaks(), vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0]);
+    assert_eq!(spline.coefs.len(), 5 + 2);
+    assert_eq!(spline.values(x.len()).len(), x.len());
+
+    // the basis sums to one, so least-squares residuals sum to zero
+    let sum = spline.residuals(&x).iter().sum::<f64>();
+    assert!(sum.abs() < 1e-9, "{sum}");
+
+    // the derivative of a quadratic spline is continuous at the breaks
+    let d = spline.derivative();
+    assert_eq!(d.degree, 1);
+    assert!((d.eval(40.0 - 1e-9) - d.eval(40.0 + 1e-9)).abs() < 1e-6);
+}
+
+#[test]
+fn test_periodicity_spline() {
+    let x = (0..10).map(|i| (i * i % 7) as f64).collect::<Vec<f64>>();
+    let spline = splinefit(&x).unwrap();
+
+    assert_eq!(spline.breaks(), vec![0.0, 4.0, 9.0]);
+    assert_eq!(spline.degree, 3);
+}
+
+#[test]
+fn test_short_input() {
+    assert_eq!(
+        splinefit(&[1.0, 2.0, 3.0]),
+        Err(Error::TooShort { len: 3, min: 5 })
+    );
+    assert_eq!(
+        BSpline::fit(&[1.0, 2.0, 3.0], 3, 3),
+        Err(Error::TooShort { len: 3, min: 5 })
+    );
+    assert_eq!(
+        BSpline::fit(&[1.0], 1, 2),
+        Err(Error::TooShort { len: 1, min: 2 })
+    );
+    assert_eq!(
+        BSpline::fit(&[1.0], 0, 2),
+        Err(Error::TooShort { len: 1, min: 2 })
+    );
+    assert!(matches!(
+        BSpline::fit_with_breaks(&[1.0; 10], 1, &[0.0, 5.0, 5.0, 9.0]),
+        Err(Error::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn test_singular_fit() {
+    // the hat function peaking at 0.5 vanishes at every sample
+    let x = (0..10).map(|i| i as f64).collect::<Vec<f64>>();
+    assert_eq!(
+        BSpline::fit_with_breaks(&x, 1, &[0.0, 0.5, 1.0, 9.0]),
+        Err(Error::SingularSystem)
+    );
+}
+
+#[test]
+fn test_large_offset() {
+    // the design matrix is factorised rather than squared into the normal equations
+    let n = 5000;
+    let x = (0..n)
+        .map(|i| {
+            let t = i as f64 / n as f64;
+            1e8 + 3.0 * t * t * t - t
+        })
+        .collect::<Vec<f64>>();
+    let spline = BSpline::fit(&x, 3, 30).unwrap();
+    for r in spline.residuals(&x) {
+        assert!(r.abs() < 1e-6, "{r}");
+    }
+}
+
+#[test]
+fn test_long_input() {
+    let n = 200_000;
+    let x = (0..n)
+        .map(|i| {
+            let t = i as f64 / n as f64;
+            1e3 + 5.0 * t * t + (t * 40.0).sin()
+        })
+        .collect::<Vec<f64>>();
+    let spline = BSpline::fit(&x, 3, 201).unwrap();
+
+    let rms = (spline.residuals(&x).iter().map(|r| r * r).sum::<f64>() / n as f64).sqrt();
+    assert!(rms < 1e-4, "{rms}");
+}
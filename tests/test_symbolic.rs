@@ -0,0 +1,78 @@
+use catch22::Error;
+use catch22::symbolic::{Method, SymbolicConfig, coarse_grain, decode_word, symbolic, word_counts};
+
+#[test]
+fn test_coarse_grain_methods() {
+    let x = (0..9).map(|i| i as f64).collect::<Vec<f64>>();
+
+    assert_eq!(
+        coarse_grain(&x, 3, Method::Quantile),
+        vec![0, 0, 0, 1, 1, 1, 2, 2, 2]
+    );
+    assert_eq!(
+        coarse_grain(&x, 4, Method::EqualWidth),
+        vec![0, 0, 1, 1, 2, 2, 3, 3, 3]
+    );
+    // symmetric around the mean, so the middle value is split evenly by the Gaussian breakpoints
+    assert_eq!(
+        coarse_grain(&x, 3, Method::Sax),
+        vec![0, 0, 0, 1, 1, 1, 2, 2, 2]
+    );
+    assert_eq!(coarse_grain(&[1.0; 5], 3, Method::Sax), vec![1; 5]);
+}
+
+#[test]
+fn test_words() {
+    let symbols = vec![0, 1, 0, 1, 1];
+    assert_eq!(word_counts(&symbols, 2, 2), vec![0, 2, 1, 1]);
+    assert_eq!(decode_word(5, 3, 2), vec![1, 2]);
+    assert_eq!(decode_word(5, 2, 3), vec![1, 0, 1]);
+}
+
+#[test]
+fn test_word_statistics() {
+    // period-3 cycle: every word of a given length is one of three equally likely words
+    let x = (0..300).map(|i| (i % 3) as f64).collect::<Vec<f64>>();
+    let config = SymbolicConfig {
+        alphabet_size: 3,
+        method: Method::EqualWidth,
+        max_word_len: 3,
+    };
+    let result = symbolic(&x, &config).unwrap();
+
+    assert_eq!(result.words.len(), 3);
+    for stats in &result.words {
+        assert!((stats.entropy - 3.0f64.ln()).abs() < 1e-2);
+        assert_eq!(stats.most_frequent.len(), stats.length);
+    }
+    // the next symbol is determined by the previous one
+    assert!(result.words[1].conditional_entropy.abs() < 1e-2);
+    assert!(result.words[2].conditional_entropy.abs() < 1e-2);
+    assert_eq!(result.words[1].most_frequent, vec![0, 1]);
+}
+
+#[test]
+fn test_reproduces_motif_three() {
+    let x = (0..200)
+        .map(|i| (i as f64 * 0.37).sin() + (i as f64 * 1.3).cos())
+        .collect::<Vec<f64>>();
+    let result = symbolic(&x, &SymbolicConfig::default()).unwrap();
+
+    assert_eq!(result.words[1].entropy, catch22::compute(&x, 15));
+}
+
+#[test]
+fn test_invalid_configs() {
+    let x = (0..20).map(|i| i as f64).collect::<Vec<f64>>();
+    for (alphabet_size, max_word_len) in [(0, 2), (3, 0), (2, 64)] {
+        let config = SymbolicConfig {
+            alphabet_size,
+            max_word_len,
+            ..SymbolicConfig::default()
+        };
+        assert!(matches!(
+            symbolic(&x, &config),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+}
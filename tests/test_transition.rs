@@ -0,0 +1,73 @@
+use catch22::Error;
+use catch22::symbolic::Method;
+use catch22::transition::{Tau, TransitionConfig, transition_matrix};
+
+mod common;
+
+use common::Xorshift;
+
+#[test]
+fn test_deterministic_cycle() {
+    let x = (0..301).map(|i| (i % 3) as f64).collect::<Vec<f64>>();
+    let config = TransitionConfig {
+        groups: 3,
+        tau: Tau::Fixed(1),
+        coarse_grain_method: Method::EqualWidth,
+    };
+    let tm = transition_matrix(&x, &config).unwrap();
+
+    let p = tm.probabilities();
+    assert_eq!(p[0], vec![0.0, 1.0, 0.0]);
+    assert_eq!(p[2], vec![1.0, 0.0, 0.0]);
+    assert!(tm.entropy_rate.abs() < 1e-12);
+    assert_eq!(tm.diagonal_mass, 0.0);
+    for pi in &tm.stationary {
+        assert!((pi - 1.0 / 3.0).abs() < 1e-9);
+    }
+    // a periodic chain has eigenvalues on the unit circle
+    assert!(tm.spectral_gap.abs() < 1e-6);
+}
+
+#[test]
+fn test_two_state_chain() {
+    // stays with probability 0.9: eigenvalues 1 and 0.8
+    let mut rng = Xorshift(42);
+    let mut state = 0;
+    let mut x = Vec::new();
+    for _ in 0..20000 {
+        if rng.uniform() < 0.1 {
+            state = 1 - state;
+        }
+        x.push(state as f64);
+    }
+    let config = TransitionConfig {
+        groups: 2,
+        tau: Tau::Fixed(1),
+        coarse_grain_method: Method::EqualWidth,
+    };
+    let tm = transition_matrix(&x, &config).unwrap();
+
+    assert!((tm.spectral_gap - 0.2).abs() < 0.02, "{}", tm.spectral_gap);
+    assert!((tm.diagonal_mass - 0.9).abs() < 0.01);
+    let h = -(0.9f64 * 0.9f64.ln() + 0.1 * 0.1f64.ln());
+    assert!((tm.entropy_rate - h).abs() < 0.01);
+    assert!((tm.stationary[0] - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_reproduces_sumdiagcov() {
+    let x = (0..500)
+        .map(|i| (i as f64 * 0.1).sin() + 0.3 * (i as f64 * 2.1).cos())
+        .collect::<Vec<f64>>();
+    let tm = transition_matrix(&x, &TransitionConfig::default()).unwrap();
+
+    assert_eq!(tm.sum_diag_cov, catch22::compute(&x, 20));
+    assert_eq!(tm.matrix.len(), 3);
+    let total = tm.matrix.iter().flatten().sum::<f64>();
+    assert!((total - 1.0).abs() < 1e-12);
+
+    assert_eq!(
+        transition_matrix(&[1.0; 10], &TransitionConfig::default()),
+        Err(Error::ConstantSeries)
+    );
+}
@@ -0,0 +1,259 @@
+use catch22::classify::{Criterion, DecisionTree, ForestConfig, RandomForest, TreeConfig};
+use catch22::{Error, Features, Options, compute_all};
+
+mod common;
+
+use common::{noise, sines_and_walks};
+
+/// Features of noisy sines (label 0) and random walks (label 1).
+fn dataset(n: usize, seed: u64) -> (Vec<Features>, Vec<usize>) {
+    let (series, labels) = sines_and_walks(n, seed);
+    let options = Options::default();
+    let features = series.iter().map(|x| compute_all(x, &options)).collect();
+    (features, labels)
+}
+
+/// Vectors of two features where only `x` separates the classes at 0.5.
+fn toy(n: usize) -> (Vec<Features>, Vec<&'static str>) {
+    let mut features = Vec::new();
+    let mut labels = Vec::new();
+    for (i, e) in noise(n, 9).into_iter().enumerate() {
+        let x = i as f64 / n as f64;
+        let mut f = Features::default();
+        f.push("x", x);
+        f.push("noise", e);
+        features.push(f);
+        labels.push(if x <= 0.5 { "low" } else { "high" });
+    }
+    (features, labels)
+}
+
+#[test]
+fn test_tree_and_forest_separate_series() {
+    let (train, labels) = dataset(10, 1);
+    let (test, truth) = dataset(5, 1000);
+
+    let tree = DecisionTree::fit(&train, &labels).unwrap();
+    let forest =
+        RandomForest::fit_with(&train, &labels, &ForestConfig::default().with_n_trees(25)).unwrap();
+    assert_eq!(tree.classes(), [0, 1]);
+    assert_eq!(forest.feature_names(), train[0].names);
+    for (x, &label) in test.iter().zip(&truth) {
+        assert_eq!(tree.predict(x), label);
+        assert_eq!(forest.predict(x), label);
+        for proba in [tree.predict_proba(x), forest.predict_proba(x)] {
+            assert!((proba.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert!(proba[label] >= 0.5);
+        }
+    }
+}
+
+#[test]
+fn test_tree_splits_on_the_informative_feature() {
+    let (train, labels) = toy(40);
+    for criterion in [Criterion::Gini, Criterion::Entropy] {
+        let config = TreeConfig::default().with_criterion(criterion);
+        let tree = DecisionTree::fit_with(&train, &labels, &config).unwrap();
+        assert_eq!(tree.depth(), 1);
+        assert_eq!(tree.n_leaves(), 2);
+        assert_eq!(tree.feature_importances(), [("x", 1.0), ("noise", 0.0)]);
+
+        let mut f = Features::default();
+        f.push("x", 0.5);
+        assert_eq!(tree.predict(&f), "low");
+        // matched by name, so order and missing features do not matter
+        let mut f = Features::default();
+        f.push("noise", 0.0);
+        f.push("x", 0.6);
+        assert_eq!(tree.predict(&f), "high");
+    }
+}
+
+#[test]
+fn test_tree_limits() {
+    let (train, labels) = dataset(10, 3);
+    let config = TreeConfig::default().with_max_depth(0);
+    let stump = DecisionTree::fit_with(&train, &labels, &config).unwrap();
+    assert_eq!(stump.n_leaves(), 1);
+    assert_eq!(stump.predict_proba(&train[0]), [0.5, 0.5]);
+    assert!(stump.feature_importances().iter().all(|&(_, v)| v == 0.0));
+
+    let config = TreeConfig::default().with_min_samples_leaf(8);
+    let tree = DecisionTree::fit_with(&train, &labels, &config).unwrap();
+    assert!(tree.n_leaves() <= 2);
+}
+
+#[test]
+fn test_forest_is_reproducible_from_its_seed() {
+    let (train, labels) = dataset(8, 5);
+    let config = ForestConfig::default()
+        .with_n_trees(20)
+        .with_tree(TreeConfig::default().with_seed(42));
+    let a = RandomForest::fit_with(&train, &labels, &config).unwrap();
+    let b = RandomForest::fit_with(&train, &labels, &config).unwrap();
+    assert_eq!(a, b);
+
+    let config = config.with_tree(TreeConfig::default().with_seed(43));
+    let c = RandomForest::fit_with(&train, &labels, &config).unwrap();
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_forest_importances_and_oob_error() {
+    let (train, labels) = dataset(10, 7);
+    let forest =
+        RandomForest::fit_with(&train, &labels, &ForestConfig::default().with_n_trees(50)).unwrap();
+
+    let importances = forest.feature_importances();
+    assert_eq!(importances.len(), train[0].len());
+    for ((name, v), expected) in importances.iter().zip(&train[0].names) {
+        assert_eq!(name, expected);
+        assert!(*v >= 0.0);
+    }
+    let total = importances.iter().map(|(_, v)| v).sum::<f64>();
+    assert!((total - 1.0).abs() < 1e-12);
+
+    let oob = forest.oob_error().unwrap();
+    assert!((0.0..=0.2).contains(&oob), "{oob}");
+
+    let single =
+        RandomForest::fit_with(&train[..1], &labels[..1], &ForestConfig::default()).unwrap();
+    assert_eq!(single.oob_error(), None);
+}
+
+#[test]
+fn test_missing_values_go_right() {
+    let (mut train, labels) = toy(40);
+    // NaN in the informative feature of every "high" vector
+    for (f, &label) in train.iter_mut().zip(&labels) {
+        if label == "high" {
+            f.values[0] = f64::NAN;
+        }
+    }
+    let tree = DecisionTree::fit(&train, &labels).unwrap();
+    assert_eq!(tree.n_leaves(), 2);
+    let mut f = Features::default();
+    f.push("x", f64::NAN);
+    assert_eq!(tree.predict(&f), "high");
+    f.values[0] = 0.2;
+    assert_eq!(tree.predict(&f), "low");
+}
+
+#[test]
+fn test_invalid_training_sets() {
+    let (train, labels) = dataset(2, 5);
+    assert!(matches!(
+        DecisionTree::fit(&train, &labels[1..]),
+        Err(Error::InvalidParameter(_))
+    ));
+    assert!(matches!(
+        RandomForest::<usize>::fit(&[], &[]),
+        Err(Error::InvalidParameter(_))
+    ));
+    let empty = [Features::default(), Features::default()];
+    assert!(DecisionTree::fit(&empty, &[0, 1]).is_err());
+
+    let mut mixed = train.clone();
+    mixed[1].push("extra", 1.0);
+    assert!(DecisionTree::fit(&mixed, &labels).is_err());
+
+    let config = ForestConfig::default().with_n_trees(0);
+    assert!(RandomForest::fit_with(&train, &labels, &config).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_save_and_load() {
+    let (train, labels) = dataset(5, 11);
+    let names = labels
+        .iter()
+        .map(|&l| if l == 0 { "sine" } else { "walk" }.to_string())
+        .collect::<Vec<_>>();
+
+    let tree = DecisionTree::fit(&train, &names).unwrap();
+    let loaded = DecisionTree::<String>::load(&tree.save()).unwrap();
+    assert_eq!(loaded, tree);
+
+    let config = ForestConfig::default()
+        .with_n_trees(10)
+        .with_tree(TreeConfig::default().with_criterion(Criterion::Entropy));
+    let forest = RandomForest::fit_with(&train, &names, &config).unwrap();
+    let json = forest.save();
+    let loaded = RandomForest::<String>::load(&json).unwrap();
+    assert_eq!(loaded, forest);
+    assert_eq!(loaded.predict(&train[1]), "walk");
+
+    let json = json.replacen(r#""version":1"#, r#""version":2"#, 1);
+    assert!(matches!(
+        RandomForest::<String>::load(&json),
+        Err(Error::InvalidModel(_))
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_save_and_load_with_non_finite_features() {
+    let mut train = Vec::new();
+    let mut labels = Vec::new();
+    for (value, label) in [
+        (f64::NEG_INFINITY, 0),
+        (f64::NEG_INFINITY, 0),
+        (1.0, 1),
+        (f64::INFINITY, 2),
+        (f64::INFINITY, 2),
+        (f64::NAN, 3),
+        (f64::NAN, 3),
+    ] {
+        let mut f = Features::default();
+        f.push("x", value);
+        train.push(f);
+        labels.push(label);
+    }
+    let tree = DecisionTree::fit(&train, &labels).unwrap();
+    let loaded = DecisionTree::<usize>::load(&tree.save()).unwrap();
+    assert_eq!(loaded, tree);
+    for (f, &label) in train.iter().zip(&labels).filter(|(_, l)| **l < 2) {
+        assert_eq!(loaded.predict(f), label);
+    }
+
+    let config = ForestConfig::default().with_n_trees(10);
+    let forest = RandomForest::fit_with(&train, &labels, &config).unwrap();
+    assert_eq!(RandomForest::<usize>::load(&forest.save()).unwrap(), forest);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_load_rejects_inconsistent_trees() {
+    let (train, labels) = toy(40);
+    let labels = labels.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+    let tree = DecisionTree::fit(&train, &labels).unwrap();
+    let stored: serde_json::Value = serde_json::from_str(&tree.save()).unwrap();
+    assert_eq!(stored["tree"]["nodes"][0]["split"]["right"], 2);
+
+    let tamper = |edit: &dyn Fn(&mut serde_json::Value)| {
+        let mut json = stored.clone();
+        edit(&mut json);
+        DecisionTree::<String>::load(&json.to_string())
+    };
+    let edits: [&dyn Fn(&mut serde_json::Value); 6] = [
+        &|j| j["tree"]["nodes"][0]["split"]["right"] = 0.into(),
+        &|j| j["tree"]["nodes"][0]["split"]["left"] = 3.into(),
+        &|j| j["tree"]["nodes"][0]["split"]["feature"] = 2.into(),
+        &|j| j["tree"]["nodes"][1]["leaf"]["proba"] = serde_json::json!([1.0]),
+        &|j| j["tree"]["importances"] = serde_json::json!([1.0]),
+        &|j| j["tree"]["nodes"] = serde_json::json!([]),
+    ];
+    for edit in edits {
+        assert!(matches!(tamper(edit), Err(Error::InvalidModel(_))));
+    }
+    assert!(tamper(&|_| {}).is_ok());
+
+    let forest =
+        RandomForest::fit_with(&train, &labels, &ForestConfig::default().with_n_trees(3)).unwrap();
+    let mut json: serde_json::Value = serde_json::from_str(&forest.save()).unwrap();
+    json["trees"] = serde_json::json!([]);
+    assert!(matches!(
+        RandomForest::<String>::load(&json.to_string()),
+        Err(Error::InvalidModel(_))
+    ));
+}
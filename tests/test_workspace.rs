@@ -0,0 +1,94 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use catch22::{Catch22Workspace, compute, compute_all_into};
+
+mod common;
+
+use common::mixture;
+
+/// Counts the allocations made by the current thread, so that tests running in parallel do not
+/// interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|n| n.get())
+}
+
+fn assert_same(a: f64, b: f64, i: usize) {
+    assert!(
+        a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan()),
+        "feature {i}: {a} != {b}"
+    );
+}
+
+#[test]
+fn test_compute_all_into_does_not_allocate() {
+    let inputs = [
+        mixture(1000, 1.0),
+        mixture(300, 50.0),
+        mixture(64, 1.0),
+        mixture(777, 0.1),
+        mixture(1000, 1.0),
+    ];
+    let mut workspace = Catch22Workspace::with_capacity(1000);
+    let mut out = [0.0; 24];
+
+    for x in &inputs {
+        let before = allocations();
+        compute_all_into(&mut workspace, x, &mut out);
+        let made = allocations() - before;
+        assert_eq!(made, 0, "{made} allocations for a series of {}", x.len());
+
+        for (i, &value) in out.iter().enumerate() {
+            assert_same(value, compute(x, i), i);
+        }
+    }
+}
+
+#[test]
+fn test_growing_workspace() {
+    let mut workspace = Catch22Workspace::new();
+    let mut out = [0.0; 24];
+
+    for n in [50, 400, 120] {
+        let x = mixture(n, 2.0);
+        let before = allocations();
+        compute_all_into(&mut workspace, &x, &mut out);
+        if n != 120 {
+            assert!(allocations() > before, "an empty workspace grows on demand");
+        }
+        for (i, &value) in out.iter().enumerate() {
+            assert_same(value, compute(&x, i), i);
+        }
+    }
+
+    // the buffers have grown to fit the longest series
+    let x = mixture(400, 2.0);
+    let before = allocations();
+    compute_all_into(&mut workspace, &x, &mut out);
+    assert_eq!(allocations() - before, 0);
+}